@@ -0,0 +1,152 @@
+// Atmosphere model and transmittance LUT parameterization from "A Scalable and Production Ready
+// Sky and Atmosphere Rendering Technique" by Sebastien Hillaire (EGSR 2020). Distances are in
+// kilometers and positions are relative to the planet center with +y up.
+//
+// Passes which render from a viewpoint define ATMOSPHERE_VIEW before including this file, and the
+// aerial perspective pass also defines ATMOSPHERE_AERIAL_PERSPECTIVE.
+
+layout(push_constant) uniform PushConstants {
+    layout(offset = 0) vec3 rayleigh_scattering;
+    layout(offset = 12) float rayleigh_scale_height;
+    layout(offset = 16) vec3 ozone_absorption;
+    layout(offset = 28) float mie_scattering;
+    layout(offset = 32) vec3 ground_albedo;
+    layout(offset = 44) float mie_extinction;
+    layout(offset = 48) float mie_scale_height;
+    layout(offset = 52) float bottom_radius;
+    layout(offset = 56) float top_radius;
+#ifdef ATMOSPHERE_VIEW
+    layout(offset = 64) vec3 sun_direction;
+    layout(offset = 80) vec3 camera_position;
+#endif
+#ifdef ATMOSPHERE_AERIAL_PERSPECTIVE
+    layout(offset = 92) float max_distance;
+#endif
+} push_const;
+
+const float PI = 3.14159265359;
+
+// Ozone density is a tent function centered at 25 km with a half width of 15 km
+const float OZONE_CENTER_HEIGHT = 25.0;
+const float OZONE_HALF_WIDTH = 15.0;
+
+// Asymmetry of the Cornette-Shanks Mie phase function
+const float MIE_G = 0.8;
+
+struct Medium {
+    vec3 rayleigh_scattering;
+    float mie_scattering;
+    vec3 scattering;
+    vec3 extinction;
+};
+
+Medium sample_medium(float radius)
+{
+    float height = max(radius - push_const.bottom_radius, 0.0);
+    float rayleigh_density = exp(-height / push_const.rayleigh_scale_height);
+    float mie_density = exp(-height / push_const.mie_scale_height);
+    float ozone_density = max(1.0 - abs(height - OZONE_CENTER_HEIGHT) / OZONE_HALF_WIDTH, 0.0);
+
+    Medium medium;
+    medium.rayleigh_scattering = push_const.rayleigh_scattering * rayleigh_density;
+    medium.mie_scattering = push_const.mie_scattering * mie_density;
+    medium.scattering = medium.rayleigh_scattering + medium.mie_scattering;
+    medium.extinction = medium.rayleigh_scattering
+        + push_const.mie_extinction * mie_density
+        + push_const.ozone_absorption * ozone_density;
+
+    return medium;
+}
+
+// Returns the distance to the nearest intersection in front of the ray origin, or -1.0
+float ray_sphere(vec3 origin, vec3 direction, float sphere_radius)
+{
+    float b = dot(origin, direction);
+    float c = dot(origin, origin) - sphere_radius * sphere_radius;
+    float discriminant = b * b - c;
+
+    if (discriminant < 0.0) {
+        return -1.0;
+    }
+
+    float root = sqrt(discriminant);
+    float near_distance = -b - root;
+    float far_distance = -b + root;
+
+    if (near_distance >= 0.0) {
+        return near_distance;
+    }
+
+    return far_distance >= 0.0 ? far_distance : -1.0;
+}
+
+// Maps a height and cosine of the view zenith angle onto transmittance LUT coordinates
+vec2 transmittance_lut_uv(float radius, float mu)
+{
+    float bottom = push_const.bottom_radius;
+    float top = push_const.top_radius;
+    float horizon = sqrt(top * top - bottom * bottom);
+    float rho = sqrt(max(radius * radius - bottom * bottom, 0.0));
+    float discriminant = radius * radius * (mu * mu - 1.0) + top * top;
+    float distance_to_top = max(-radius * mu + sqrt(max(discriminant, 0.0)), 0.0);
+    float distance_min = top - radius;
+    float distance_max = rho + horizon;
+
+    return vec2((distance_to_top - distance_min) / (distance_max - distance_min), rho / horizon);
+}
+
+// Inverse of transmittance_lut_uv, returning the height and cosine of the view zenith angle
+vec2 transmittance_lut_params(vec2 uv)
+{
+    float bottom = push_const.bottom_radius;
+    float top = push_const.top_radius;
+    float horizon = sqrt(top * top - bottom * bottom);
+    float rho = horizon * uv.y;
+    float radius = sqrt(rho * rho + bottom * bottom);
+    float distance_min = top - radius;
+    float distance_max = rho + horizon;
+    float distance_to_top = distance_min + uv.x * (distance_max - distance_min);
+    float mu = distance_to_top == 0.0
+        ? 1.0
+        : (horizon * horizon - rho * rho - distance_to_top * distance_to_top)
+            / (2.0 * radius * distance_to_top);
+
+    return vec2(radius, clamp(mu, -1.0, 1.0));
+}
+
+vec3 sun_transmittance(sampler2D transmittance_lut, vec3 position, vec3 sun_direction)
+{
+    float radius = length(position);
+    float mu = dot(position / radius, sun_direction);
+
+    // The planet shadows points where the sun is below the horizon
+    if (ray_sphere(position, sun_direction, push_const.bottom_radius) >= 0.0) {
+        return vec3(0.0);
+    }
+
+    return textureLod(transmittance_lut, transmittance_lut_uv(radius, mu), 0.0).rgb;
+}
+
+// Multiple scattering LUT coordinates are the cosine of the sun zenith angle and the height
+vec3 multiscatter_luminance(sampler2D multiscatter_lut, vec3 position, vec3 sun_direction)
+{
+    float radius = length(position);
+    float sun_mu = dot(position / radius, sun_direction);
+    float height = (radius - push_const.bottom_radius)
+        / (push_const.top_radius - push_const.bottom_radius);
+
+    return textureLod(multiscatter_lut, vec2(sun_mu * 0.5 + 0.5, height), 0.0).rgb;
+}
+
+float rayleigh_phase(float cos_theta)
+{
+    return 3.0 / (16.0 * PI) * (1.0 + cos_theta * cos_theta);
+}
+
+float mie_phase(float cos_theta)
+{
+    float g2 = MIE_G * MIE_G;
+    float k = 3.0 / (8.0 * PI) * (1.0 - g2) / (2.0 + g2);
+
+    return k * (1.0 + cos_theta * cos_theta) / pow(1.0 + g2 - 2.0 * MIE_G * cos_theta, 1.5);
+}
@@ -0,0 +1,9 @@
+// Circle of confusion shared by the gather-based depth of field passes, which all bind it at set 0
+// binding 1; its absolute value is the blur radius relative to the maximum radius
+layout(set = 0, binding = 1, r16f) restrict readonly uniform image2D coc_image;
+
+// Returns the circle of confusion radius of a texel in texels
+float coc_radius(ivec2 coord, float max_radius)
+{
+    return abs(imageLoad(coc_image, coord).r) * max_radius;
+}
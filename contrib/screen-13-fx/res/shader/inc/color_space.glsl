@@ -14,4 +14,10 @@ vec3 linear_to_srgb(vec3 val)
         linear_to_srgb(val.y),
         linear_to_srgb(val.z)
     );
-}
\ No newline at end of file
+}
+
+// Relative luminance of a linear Rec. 709 color
+float luminance(vec3 val)
+{
+    return dot(val, vec3(0.2126, 0.7152, 0.0722));
+}
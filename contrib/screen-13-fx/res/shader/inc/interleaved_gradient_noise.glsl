@@ -0,0 +1,6 @@
+// Interleaved gradient noise from "Next Generation Post Processing in Call of Duty: Advanced
+// Warfare" by Jimenez (SIGGRAPH 2014)
+float interleaved_gradient_noise(vec2 coord)
+{
+    return fract(52.9829189 * fract(dot(coord, vec2(0.06711056, 0.00583715))));
+}
@@ -0,0 +1,31 @@
+vec2 sign_not_zero(vec2 v)
+{
+    return vec2(v.x >= 0.0 ? 1.0 : -1.0, v.y >= 0.0 ? 1.0 : -1.0);
+}
+
+// Maps a unit vector onto the [-1, 1] square by projecting it onto an octahedron and folding the
+// lower hemisphere over the upper one
+vec2 encode_octahedral(vec3 n)
+{
+    n /= abs(n.x) + abs(n.y) + abs(n.z);
+
+    vec2 encoded = n.xy;
+
+    if (n.z < 0.0) {
+        encoded = (1.0 - abs(n.yx)) * sign_not_zero(n.xy);
+    }
+
+    return encoded;
+}
+
+// Reverses encode_octahedral, unfolding the lower hemisphere
+vec3 decode_octahedral(vec2 encoded)
+{
+    vec3 n = vec3(encoded, 1.0 - abs(encoded.x) - abs(encoded.y));
+
+    if (n.z < 0.0) {
+        n.xy = (1.0 - abs(n.yx)) * sign_not_zero(n.xy);
+    }
+
+    return normalize(n);
+}
@@ -0,0 +1,10 @@
+// Tightly packed `[f32; 3]` vertex positions, which the mesh processing passes all bind at set 0
+// binding 0; std430 would pad a `vec3` array to 16 bytes per element, so floats are read instead
+layout(set = 0, binding = 0, std430) restrict readonly buffer PositionBuffer {
+    float positions[];
+};
+
+vec3 position(uint vertex)
+{
+    return vec3(positions[vertex * 3], positions[vertex * 3 + 1], positions[vertex * 3 + 2]);
+}
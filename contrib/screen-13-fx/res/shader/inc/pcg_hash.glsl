@@ -0,0 +1,8 @@
+// PCG hash from "Hash Functions for GPU Rendering" by Jarzynski and Olano (JCGT 2020)
+uint pcg_hash(uint v)
+{
+    uint state = v * 747796405u + 2891336453u;
+    uint word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+
+    return (word >> 22u) ^ word;
+}
@@ -0,0 +1,10 @@
+// Reconstructs a view space position, looking down negative z, from the texture coordinates and
+// linear view depth of a texel rendered with the given perspective projection
+vec3 view_position(vec2 uv, float depth, mat4 projection)
+{
+    vec2 ndc = uv * 2.0 - 1.0;
+    vec2 xy = (ndc + vec2(projection[2][0], projection[2][1])) * depth
+        / vec2(projection[0][0], projection[1][1]);
+
+    return vec3(xy, -depth);
+}
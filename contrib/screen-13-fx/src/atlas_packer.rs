@@ -0,0 +1,57 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Copies rasterized glyphs or sprites into a shared atlas image as a single compute pass, for
+/// dynamic text.
+///
+/// The glyph buffer is a storage buffer of tightly packed 8-bit coverage values, which may hold
+/// the pixels of many glyphs one after another. The rect buffer is a storage buffer holding five
+/// `u32` values for each glyph: the byte offset of its first pixel in the glyph buffer, the `x`
+/// and `y` texel of its top-left corner in the atlas, and its width and height. Pixels of each
+/// glyph are stored in rows. The atlas image must be an `r8` storage image; glyph pixels which
+/// fall outside of it are skipped and texels not covered by a glyph are left unchanged.
+pub struct AtlasPacker {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl AtlasPacker {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/pack_atlas.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads the first `glyph_count` rects of `rect_buf` and the pixels of
+    /// `glyph_buf` and writes them to `atlas_image`.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        glyph_buf: impl Into<AnyBufferNode>,
+        rect_buf: impl Into<AnyBufferNode>,
+        atlas_image: impl Into<AnyImageNode>,
+        glyph_count: u32,
+    ) {
+        let glyph_buf = glyph_buf.into();
+        let rect_buf = rect_buf.into();
+        let atlas_image = atlas_image.into();
+
+        debug_assert!(render_graph.node_info(rect_buf).size >= glyph_count as vk::DeviceSize * 20);
+
+        render_graph
+            .begin_pass("pack atlas")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, glyph_buf)
+            .read_descriptor(1, rect_buf)
+            .write_descriptor(2, atlas_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[glyph_count]))
+                    .dispatch(glyph_count, 1, 1);
+            });
+    }
+}
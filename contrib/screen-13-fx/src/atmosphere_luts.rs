@@ -0,0 +1,287 @@
+use {
+    bytemuck::cast_slice, glam::Vec3, inline_spirv::include_spirv, screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Physical description of a planetary atmosphere, used by [`AtmosphereLuts`].
+///
+/// Distances are in kilometers and coefficients are per kilometer. The default is an Earth-like
+/// atmosphere.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Atmosphere {
+    /// Radius of the planet surface.
+    pub bottom_radius: f32,
+
+    /// Radius of the top of the atmosphere.
+    pub top_radius: f32,
+
+    /// Rayleigh scattering coefficients for red, green and blue light at sea level.
+    pub rayleigh_scattering: Vec3,
+
+    /// Height at which Rayleigh scattering has fallen to `1/e` of its sea level value.
+    pub rayleigh_scale_height: f32,
+
+    /// Mie scattering coefficient at sea level.
+    pub mie_scattering: f32,
+
+    /// Mie extinction coefficient (scattering plus absorption) at sea level.
+    pub mie_extinction: f32,
+
+    /// Height at which Mie scattering has fallen to `1/e` of its sea level value.
+    pub mie_scale_height: f32,
+
+    /// Ozone absorption coefficients for red, green and blue light at the peak of the ozone layer,
+    /// which is modelled as a layer between 10 and 40 kilometers.
+    pub ozone_absorption: Vec3,
+
+    /// Average albedo of the planet surface, which contributes to multiple scattering.
+    pub ground_albedo: Vec3,
+}
+
+impl Atmosphere {
+    fn push_constants(&self) -> Vec<u8> {
+        let mut push_consts = Vec::with_capacity(60);
+        push_consts.extend_from_slice(cast_slice(&self.rayleigh_scattering.to_array()));
+        push_consts.extend_from_slice(&self.rayleigh_scale_height.to_ne_bytes());
+        push_consts.extend_from_slice(cast_slice(&self.ozone_absorption.to_array()));
+        push_consts.extend_from_slice(&self.mie_scattering.to_ne_bytes());
+        push_consts.extend_from_slice(cast_slice(&self.ground_albedo.to_array()));
+        push_consts.extend_from_slice(&self.mie_extinction.to_ne_bytes());
+        push_consts.extend_from_slice(&self.mie_scale_height.to_ne_bytes());
+        push_consts.extend_from_slice(&self.bottom_radius.to_ne_bytes());
+        push_consts.extend_from_slice(&self.top_radius.to_ne_bytes());
+        push_consts
+    }
+
+    fn view_push_constants(&self, sun_direction: Vec3, camera_position: Vec3) -> Vec<u8> {
+        let mut push_consts = self.push_constants();
+        push_consts.reserve(36);
+        push_consts.extend_from_slice(&[0; 4]);
+        push_consts.extend_from_slice(cast_slice(&sun_direction.to_array()));
+        push_consts.extend_from_slice(&[0; 4]);
+        push_consts.extend_from_slice(cast_slice(&camera_position.to_array()));
+        push_consts
+    }
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self {
+            bottom_radius: 6360.0,
+            top_radius: 6460.0,
+            rayleigh_scattering: Vec3::new(5.802e-3, 13.558e-3, 33.1e-3),
+            rayleigh_scale_height: 8.0,
+            mie_scattering: 3.996e-3,
+            mie_extinction: 4.44e-3,
+            mie_scale_height: 1.2,
+            ozone_absorption: Vec3::new(0.65e-3, 1.881e-3, 0.085e-3),
+            ground_albedo: Vec3::splat(0.3),
+        }
+    }
+}
+
+/// Precomputes the transmittance and multiple scattering lookup tables used to render a
+/// physically based sky, following "A Scalable and Production Ready Sky and Atmosphere Rendering
+/// Technique" by Sébastien Hillaire (EGSR 2020), and renders the per-frame sky-view and aerial
+/// perspective tables from them.
+///
+/// All tables are `rgba16f` storage images. Transmittance and multiple scattering only need to be
+/// recomputed when the [`Atmosphere`] changes, while the sky-view and aerial perspective tables
+/// depend on the sun and camera and are usually rendered every frame. Each pass covers the whole
+/// destination image in 8x8 workgroups, so any size may be used; 256x64 texels for transmittance,
+/// 32x32 texels for multiple scattering, 192x108 texels for the sky view and 32x32x32 texels for
+/// aerial perspective are good defaults.
+pub struct AtmosphereLuts {
+    aerial_perspective_pipeline: Arc<ComputePipeline>,
+    multiscatter_pipeline: Arc<ComputePipeline>,
+    sky_view_pipeline: Arc<ComputePipeline>,
+    transmittance_pipeline: Arc<ComputePipeline>,
+}
+
+impl AtmosphereLuts {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            aerial_perspective_pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/render_aerial_perspective.comp", comp)
+                        .as_slice(),
+                ),
+            )?),
+            multiscatter_pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/precompute_multiscatter_lut.comp", comp)
+                        .as_slice(),
+                ),
+            )?),
+            sky_view_pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/render_sky_view_lut.comp", comp).as_slice(),
+                ),
+            )?),
+            transmittance_pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/precompute_transmittance_lut.comp", comp)
+                        .as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `transmittance_image`, `multiscatter_image` and `camera_buf` and
+    /// writes `aerial_perspective_image`.
+    ///
+    /// The transmittance and multiple scattering images must have been written using the same
+    /// `atmosphere` and are sampled, so must have been created with `SAMPLED` usage. The camera
+    /// buffer is a storage buffer holding the column-major inverse view-projection `mat4`.
+    ///
+    /// The aerial perspective image is a 3D image covering the view frustum: `x` and `y` follow
+    /// the screen and the slices along `z` are spaced linearly from the camera out to
+    /// `max_distance`. Each texel holds the luminance scattered towards the camera by the air in
+    /// front of it in `rgb` and the mean transmittance in `a`, for a sun of unit illuminance.
+    ///
+    /// `sun_direction` points towards the sun and `camera_position` and `max_distance` are in
+    /// kilometers, with the planet surface at `y = 0`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn aerial_perspective(
+        &self,
+        render_graph: &mut RenderGraph,
+        transmittance_image: impl Into<AnyImageNode>,
+        multiscatter_image: impl Into<AnyImageNode>,
+        camera_buf: impl Into<AnyBufferNode>,
+        aerial_perspective_image: impl Into<AnyImageNode>,
+        atmosphere: &Atmosphere,
+        sun_direction: Vec3,
+        camera_position: Vec3,
+        max_distance: f32,
+    ) {
+        let transmittance_image = transmittance_image.into();
+        let multiscatter_image = multiscatter_image.into();
+        let camera_buf = camera_buf.into();
+        let aerial_perspective_image = aerial_perspective_image.into();
+        let aerial_perspective_info = render_graph.node_info(aerial_perspective_image);
+        let mut push_consts = atmosphere.view_push_constants(sun_direction, camera_position);
+        push_consts.extend_from_slice(&max_distance.to_ne_bytes());
+
+        debug_assert!(render_graph.node_info(camera_buf).size >= 64);
+
+        render_graph
+            .begin_pass("render aerial perspective")
+            .bind_pipeline(&self.aerial_perspective_pipeline)
+            .read_descriptor(0, transmittance_image)
+            .read_descriptor(1, multiscatter_image)
+            .read_descriptor(2, camera_buf)
+            .write_descriptor(3, aerial_perspective_image)
+            .record_compute(move |compute, _| {
+                compute.push_constants(&push_consts).dispatch_3d(
+                    aerial_perspective_info.width,
+                    aerial_perspective_info.height,
+                    aerial_perspective_info.depth,
+                );
+            });
+    }
+
+    /// Records a pass which reads `transmittance_image` and writes `multiscatter_image`.
+    ///
+    /// The transmittance image must have been written by [`AtmosphereLuts::transmittance`] using
+    /// the same `atmosphere`, and is sampled so must have been created with `SAMPLED` usage. The
+    /// `x` axis of the multiple scattering table is the cosine of the sun zenith angle, from `-1`
+    /// to `1`, and the `y` axis is the height from the planet surface to the top of the atmosphere.
+    pub fn multiscatter(
+        &self,
+        render_graph: &mut RenderGraph,
+        transmittance_image: impl Into<AnyImageNode>,
+        multiscatter_image: impl Into<AnyImageNode>,
+        atmosphere: &Atmosphere,
+    ) {
+        let transmittance_image = transmittance_image.into();
+        let multiscatter_image = multiscatter_image.into();
+        let multiscatter_info = render_graph.node_info(multiscatter_image);
+        let push_consts = atmosphere.push_constants();
+
+        render_graph
+            .begin_pass("precompute multiscatter lut")
+            .bind_pipeline(&self.multiscatter_pipeline)
+            .read_descriptor(0, transmittance_image)
+            .write_descriptor(1, multiscatter_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(multiscatter_info.width, multiscatter_info.height);
+            });
+    }
+
+    /// Records a pass which reads `transmittance_image` and `multiscatter_image` and writes
+    /// `sky_view_image`.
+    ///
+    /// The transmittance and multiple scattering images must have been written using the same
+    /// `atmosphere` and are sampled, so must have been created with `SAMPLED` usage. The `x` axis
+    /// of the sky-view table is the azimuth relative to the sun, from `0` to `pi`, and the `y`
+    /// axis is the view zenith angle with the horizon at the center row and more detail close to
+    /// it. Each texel holds the sky luminance for a sun of unit illuminance.
+    ///
+    /// `sun_direction` points towards the sun and `camera_position` is in kilometers, with the
+    /// planet surface at `y = 0`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn sky_view(
+        &self,
+        render_graph: &mut RenderGraph,
+        transmittance_image: impl Into<AnyImageNode>,
+        multiscatter_image: impl Into<AnyImageNode>,
+        sky_view_image: impl Into<AnyImageNode>,
+        atmosphere: &Atmosphere,
+        sun_direction: Vec3,
+        camera_position: Vec3,
+    ) {
+        let transmittance_image = transmittance_image.into();
+        let multiscatter_image = multiscatter_image.into();
+        let sky_view_image = sky_view_image.into();
+        let sky_view_info = render_graph.node_info(sky_view_image);
+        let push_consts = atmosphere.view_push_constants(sun_direction, camera_position);
+
+        render_graph
+            .begin_pass("render sky view lut")
+            .bind_pipeline(&self.sky_view_pipeline)
+            .read_descriptor(0, transmittance_image)
+            .read_descriptor(1, multiscatter_image)
+            .write_descriptor(2, sky_view_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(sky_view_info.width, sky_view_info.height);
+            });
+    }
+
+    /// Records a pass which writes `transmittance_image`.
+    ///
+    /// The table uses the height and view zenith parameterization of Bruneton and Neyret, in which
+    /// the `x` axis maps the distance to the top of the atmosphere and the `y` axis maps the
+    /// height, so that the horizon is resolved with the most detail.
+    pub fn transmittance(
+        &self,
+        render_graph: &mut RenderGraph,
+        transmittance_image: impl Into<AnyImageNode>,
+        atmosphere: &Atmosphere,
+    ) {
+        let transmittance_image = transmittance_image.into();
+        let transmittance_info = render_graph.node_info(transmittance_image);
+        let push_consts = atmosphere.push_constants();
+
+        render_graph
+            .begin_pass("precompute transmittance lut")
+            .bind_pipeline(&self.transmittance_pipeline)
+            .write_descriptor(0, transmittance_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(transmittance_info.width, transmittance_info.height);
+            });
+    }
+}
@@ -0,0 +1,52 @@
+use {screen_13::prelude::*, std::sync::Arc};
+
+/// A single `u32` storage buffer used as an atomic append counter by GPU-driven compute passes.
+///
+/// Compute shaders which append into a buffer should declare the counter as a read-write storage
+/// buffer containing one `uint` and increment it with `atomicAdd`:
+///
+/// ```glsl
+/// layout(set = 0, binding = 0) restrict buffer Counter {
+///     uint count;
+/// } counter;
+///
+/// uint idx = atomicAdd(counter.count, 1);
+/// ```
+///
+/// Call [`AtomicCounterBuffer::reset`] before the appending pass and bind the returned node using
+/// `access_descriptor(.., AccessType::ComputeShaderReadWrite)`, because `atomicAdd` reads the
+/// counter as well as writing it.
+pub struct AtomicCounterBuffer {
+    buffer: Arc<Buffer>,
+}
+
+impl AtomicCounterBuffer {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            buffer: Arc::new(Buffer::create(
+                device,
+                BufferInfo::device_mem(
+                    size_of::<u32>() as vk::DeviceSize,
+                    vk::BufferUsageFlags::STORAGE_BUFFER
+                        | vk::BufferUsageFlags::TRANSFER_DST
+                        | vk::BufferUsageFlags::TRANSFER_SRC,
+                ),
+            )?),
+        })
+    }
+
+    /// The underlying buffer, which may be bound directly when the counter should not be reset.
+    pub fn buffer(&self) -> &Arc<Buffer> {
+        &self.buffer
+    }
+
+    /// Binds the counter to `render_graph` and records a fill which sets it to zero.
+    ///
+    /// The returned node must be used for all further access to the counter within this graph.
+    pub fn reset(&self, render_graph: &mut RenderGraph) -> BufferNode {
+        let buffer = render_graph.bind_node(&self.buffer);
+        render_graph.fill_buffer(buffer, 0);
+
+        buffer
+    }
+}
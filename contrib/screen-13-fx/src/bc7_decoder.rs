@@ -0,0 +1,57 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Decodes BC7 compressed texture data on the GPU, for devices which do not sample BC7 natively.
+///
+/// The source is a storage buffer of 16-byte BC7 blocks in row-major block order and the
+/// destination is an `rgba8ui` storage image (such as `R8G8B8A8_UINT`) of the decoded size, which
+/// may then be copied into an `R8G8B8A8_UNORM` or `R8G8B8A8_SRGB` image.
+pub struct Bc7Decoder {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl Bc7Decoder {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/decode_bc7.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which decodes every block of `block_buf` into `dst_image`.
+    ///
+    /// The size of `dst_image` determines the decoded size; partial blocks on the right and bottom
+    /// edges are cropped.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        block_buf: impl Into<AnyBufferNode>,
+        dst_image: impl Into<AnyImageNode>,
+    ) {
+        let block_buf = block_buf.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        debug_assert!(
+            render_graph.node_info(block_buf).size
+                >= (dst_info.width.div_ceil(4) * dst_info.height.div_ceil(4) * 16)
+                    as vk::DeviceSize,
+            "insufficient data"
+        );
+
+        render_graph
+            .begin_pass("decode bc7")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, block_buf)
+            .write_descriptor(1, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[dst_info.width, dst_info.height]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,55 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Adds a blurred bloom image back onto the scene, as a single compute pass.
+///
+/// The base and destination images must be `rgba16f` storage images of the same size. The bloom
+/// image is sampled and so must have been created with `SAMPLED` usage; it may be smaller than
+/// the destination, in which case it is bilinearly upsampled. Together with
+/// [`BloomPrefilter`](crate::BloomPrefilter) and [`KawaseBlur`](crate::KawaseBlur) this forms a
+/// complete bloom chain.
+pub struct BloomCombine {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl BloomCombine {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/bloom_combine.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `base_image` and `bloom_image` and writes `dst_image`.
+    ///
+    /// The bloom image is scaled by `intensity` before it is added to the base image.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        base_image: impl Into<AnyImageNode>,
+        bloom_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        intensity: f32,
+    ) {
+        let base_image = base_image.into();
+        let bloom_image = bloom_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        render_graph
+            .begin_pass("bloom combine")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, base_image)
+            .read_descriptor(1, bloom_image)
+            .write_descriptor(2, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[intensity]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,55 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Extracts the bright areas of an HDR image for bloom, as a single compute pass.
+///
+/// Texels are attenuated by a quadratic soft-knee threshold curve so that bloom fades in smoothly
+/// instead of switching on at a hard cutoff. The source and destination images must be `rgba16f`
+/// storage images of the same size. The output is intended to be downsampled and blurred, for
+/// example using [`KawaseBlur`](crate::KawaseBlur).
+pub struct BloomPrefilter {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl BloomPrefilter {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/bloom_prefilter.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `src_image` and writes the thresholded `dst_image`.
+    ///
+    /// Texels brighter than `threshold` pass through, and `knee` is the half-width of the soft
+    /// transition around it (`0.5 * threshold` is a good default). Texels brighter than
+    /// `max_brightness` are scaled down to it to suppress fireflies; `0.0` disables the clamp.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        threshold: f32,
+        knee: f32,
+        max_brightness: f32,
+    ) {
+        let src_image = src_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        render_graph
+            .begin_pass("bloom prefilter")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, src_image)
+            .write_descriptor(1, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[threshold, knee, max_brightness]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,47 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Generates a tileable blue noise texture, suitable for dithering, as a single compute pass.
+///
+/// The noise is an approximation made by high-pass filtering white noise; it is cheap to generate
+/// but not as evenly distributed as noise made offline using void-and-cluster. The destination
+/// image must be an `r8` storage image.
+pub struct BlueNoiseGenerator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl BlueNoiseGenerator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/generate_blue_noise.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which fills `dst_image` with blue noise.
+    ///
+    /// Each `seed` value produces a different, but repeatable, noise pattern.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        dst_image: impl Into<AnyImageNode>,
+        seed: u32,
+    ) {
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        render_graph
+            .begin_pass("generate blue noise")
+            .bind_pipeline(&self.pipeline)
+            .write_descriptor(0, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[dst_info.width, dst_info.height, seed]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,51 @@
+use {inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Computes the bounding sphere of a vertex buffer, for GPU-driven culling, as a single compute
+/// pass.
+///
+/// Positions are read as tightly packed `[f32; 3]` values and the sphere is written as a
+/// `[f32; 4]` value holding the center followed by the radius. The sphere is centered on the
+/// axis-aligned bounds of the positions, which is not always the tightest possible sphere but is
+/// cheap to find. The pass runs as a single workgroup, so it suits per-object meshes rather than
+/// very large buffers.
+pub struct BoundsCalculator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl BoundsCalculator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/compute_bounds.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads the first `vertex_count` positions of `position_buf` and writes
+    /// their bounding sphere to `bounds_buf`.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        position_buf: impl Into<AnyBufferNode>,
+        bounds_buf: impl Into<AnyBufferNode>,
+        vertex_count: u32,
+    ) {
+        let position_buf = position_buf.into();
+        let bounds_buf = bounds_buf.into();
+
+        render_graph
+            .begin_pass("compute bounds")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, position_buf)
+            .write_descriptor(1, bounds_buf)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&vertex_count.to_ne_bytes())
+                    .dispatch(1, 1, 1);
+            });
+    }
+}
@@ -0,0 +1,167 @@
+use {
+    inline_spirv::include_spirv,
+    log::warn,
+    screen_13::prelude::*,
+    std::{iter::successors, ops::Range, sync::Arc},
+};
+
+/// Reduces a buffer of values to a single value using an associative operation, as a chain of
+/// compute passes.
+///
+/// The operation is provided as a compute shader which reads an array of `count` elements from the
+/// storage buffer at binding `0`, where `count` is a `uint` push constant at offset `0`. Each
+/// workgroup reduces `local_size_x` consecutive elements and writes the result to the element of
+/// the storage buffer at binding `1` indexed by `gl_WorkGroupID.x`. Invocations past the end of
+/// the input must contribute the identity of the operation. The workgroup size must be declared
+/// with literal values and `local_size_y` and `local_size_z` must be `1`.
+///
+/// See `res/shader/compute/reduce_sum_f32.comp` for a complete example, which is also available
+/// using [`BufferReduction::sum_f32`].
+pub struct BufferReduction {
+    cache: HashPool,
+    group_size: u32,
+    offset_alignment: vk::DeviceSize,
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl BufferReduction {
+    /// Creates a reduction from a compute shader which follows the layout described above.
+    pub fn new(device: &Arc<Device>, shader: impl Into<Shader>) -> Result<Self, DriverError> {
+        let pipeline =
+            ComputePipeline::create(device, ComputePipelineInfo::default(), shader.into())?;
+        let group_size = match ComputePipeline::local_size(&pipeline) {
+            Some([group_size, 1, 1]) if group_size > 1 => group_size,
+            local_size => {
+                warn!("unsupported reduction workgroup size: {local_size:?}");
+
+                return Err(DriverError::InvalidData);
+            }
+        };
+
+        Ok(Self {
+            cache: HashPool::new(device),
+            group_size,
+            offset_alignment: device
+                .physical_device
+                .properties_v1_0
+                .limits
+                .min_storage_buffer_offset_alignment,
+            pipeline: Arc::new(pipeline),
+        })
+    }
+
+    /// Creates a reduction which sums `f32` values.
+    pub fn sum_f32(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Self::new(
+            device,
+            Shader::new_compute(
+                include_spirv!("res/shader/compute/reduce_sum_f32.comp", comp).as_slice(),
+            ),
+        )
+    }
+
+    /// Records the passes which reduce the first `count` elements of `src_buf` into the first
+    /// element of `dst_buf`.
+    ///
+    /// `element_size` is the size in bytes of each element. Each pass reduces the element count
+    /// by a factor of the workgroup size and writes its partial results into one scratch buffer,
+    /// leased from an internal pool and sized using [`BufferReduction::scratch_buffer_size`],
+    /// which is retained across all of the passes. Barriers between the passes are handled by the
+    /// render graph.
+    ///
+    /// Returns an error if the scratch buffer cannot be leased.
+    pub fn reduce(
+        &mut self,
+        render_graph: &mut RenderGraph,
+        src_buf: impl Into<AnyBufferNode>,
+        dst_buf: impl Into<AnyBufferNode>,
+        count: u32,
+        element_size: vk::DeviceSize,
+    ) -> Result<(), DriverError> {
+        debug_assert_ne!(count, 0);
+
+        let src_buf = src_buf.into();
+        let dst_buf = dst_buf.into();
+        let mut src: (_, BufferSubresourceRange) =
+            (src_buf, render_graph.node_info(src_buf).into());
+        let mut src_count = count;
+
+        let scratch_size = self.scratch_buffer_size(count, element_size);
+        if scratch_size > 0 {
+            let scratch_buf: AnyBufferNode = render_graph
+                .bind_node(self.cache.lease(BufferInfo::device_mem(
+                    scratch_size,
+                    vk::BufferUsageFlags::STORAGE_BUFFER,
+                ))?)
+                .into();
+
+            for (group_count, range) in self.scratch_ranges(count, element_size) {
+                let dst = (scratch_buf, range.into());
+                self.record(render_graph, src, dst, src_count, group_count);
+                src = dst;
+                src_count = group_count;
+            }
+        }
+
+        let dst = (dst_buf, render_graph.node_info(dst_buf).into());
+        self.record(render_graph, src, dst, src_count, 1);
+
+        Ok(())
+    }
+
+    /// Returns the size in bytes of the scratch buffer used to reduce `count` elements of
+    /// `element_size` bytes each.
+    ///
+    /// The scratch buffer holds the partial results of every pass except the last, and the
+    /// results of each pass start at an offset aligned to the minimum storage buffer offset
+    /// alignment of the device. The size is zero when `count` fits within a single workgroup.
+    pub fn scratch_buffer_size(&self, count: u32, element_size: vk::DeviceSize) -> vk::DeviceSize {
+        self.scratch_ranges(count, element_size)
+            .last()
+            .map(|(_, range)| range.end)
+            .unwrap_or_default()
+    }
+
+    fn record(
+        &self,
+        render_graph: &mut RenderGraph,
+        (src_buf, src_range): (AnyBufferNode, BufferSubresourceRange),
+        (dst_buf, dst_range): (AnyBufferNode, BufferSubresourceRange),
+        count: u32,
+        group_count: u32,
+    ) {
+        render_graph
+            .begin_pass("reduce buffer")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor_as(0, src_buf, src_range)
+            .write_descriptor_as(1, dst_buf, dst_range)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&count.to_ne_bytes())
+                    .dispatch(group_count, 1, 1);
+            });
+    }
+
+    /// Returns the group count and scratch buffer range of each pass except the last.
+    fn scratch_ranges(
+        &self,
+        count: u32,
+        element_size: vk::DeviceSize,
+    ) -> impl Iterator<Item = (u32, Range<vk::DeviceSize>)> {
+        let group_size = self.group_size;
+        let offset_alignment = self.offset_alignment;
+        let mut offset = 0;
+
+        successors(Some(count.div_ceil(group_size)), move |group_count| {
+            Some(group_count.div_ceil(group_size))
+        })
+        .take_while(|&group_count| group_count > 1)
+        .map(move |group_count| {
+            let start = offset.next_multiple_of(offset_alignment);
+            let end = start + group_count as vk::DeviceSize * element_size;
+            offset = end;
+
+            (group_count, start..end)
+        })
+    }
+}
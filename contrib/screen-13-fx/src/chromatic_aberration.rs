@@ -0,0 +1,67 @@
+use {
+    crate::{apply_effect, ImageEffect},
+    bytemuck::cast_slice,
+    inline_spirv::include_spirv,
+    screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Lens-style chromatic aberration as a single compute pass.
+///
+/// The source and destination images must be `rgba8` storage images of the same size.
+pub struct ChromaticAberration {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl ChromaticAberration {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/chromatic_aberration.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `src_image` and writes `dst_image`.
+    ///
+    /// `strength` is the radial offset of the red channel as a fraction of the distance from
+    /// `center` (`0.01` is subtle), and `center` is the normalized texture coordinate the channels
+    /// are offset away from (usually `[0.5, 0.5]`). Offsets which land outside the image are
+    /// clamped to the nearest edge.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        strength: f32,
+        center: [f32; 2],
+    ) {
+        apply_effect(
+            render_graph,
+            self,
+            src_image,
+            dst_image,
+            &(strength, center),
+        );
+    }
+}
+
+impl ImageEffect for ChromaticAberration {
+    type Params = (f32, [f32; 2]);
+
+    fn name(&self) -> &'static str {
+        "chromatic aberration"
+    }
+
+    fn pipeline(&self) -> &Arc<ComputePipeline> {
+        &self.pipeline
+    }
+
+    fn push_constants(&self, &(strength, center): &Self::Params) -> Vec<u8> {
+        cast_slice(&[center[0], center[1], strength]).to_vec()
+    }
+}
@@ -0,0 +1,78 @@
+use {
+    bytemuck::cast_slice,
+    glam::{Mat4, UVec3},
+    inline_spirv::include_spirv,
+    screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Culls point lights into a clustered (froxel) grid for clustered forward shading.
+///
+/// Lights are read from a storage buffer of `vec4` values (world-space position in `xyz` and
+/// radius in `w`) and clusters from a storage buffer of view-space AABBs, each stored as a `vec4`
+/// minimum followed by a `vec4` maximum, ordered X-major then Y then Z.
+///
+/// The light index buffer begins with a `u32` counter followed by the light indices of every
+/// cluster, and the light grid buffer receives a `uvec2` of offset and count into that list for
+/// each cluster. Lights which do not fit in the light index buffer are dropped.
+pub struct ClusteredLightCuller {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl ClusteredLightCuller {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/cull_lights_clustered.comp", comp)
+                        .as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which resets the light index counter and then culls every light in
+    /// `light_buf` against every cluster of the `cluster_count` grid.
+    ///
+    /// `view` transforms the world-space light positions into the view space of the clusters.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        light_buf: impl Into<AnyBufferNode>,
+        cluster_aabb_buf: impl Into<AnyBufferNode>,
+        light_index_buf: impl Into<AnyBufferNode>,
+        light_grid_buf: impl Into<AnyBufferNode>,
+        cluster_count: UVec3,
+        view: Mat4,
+    ) {
+        let light_buf = light_buf.into();
+        let cluster_aabb_buf = cluster_aabb_buf.into();
+        let light_index_buf = light_index_buf.into();
+        let light_grid_buf = light_grid_buf.into();
+        let light_count = (render_graph.node_info(light_buf).size / 16) as u32;
+
+        let mut push_consts = Vec::with_capacity(80);
+        push_consts.extend_from_slice(cast_slice(&cluster_count.to_array()));
+        push_consts.extend_from_slice(&light_count.to_ne_bytes());
+        push_consts.extend_from_slice(cast_slice(&view.to_cols_array()));
+
+        render_graph.fill_buffer_region(light_index_buf, 0, 0..4);
+        render_graph
+            .begin_pass("cull lights (clustered)")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, light_buf)
+            .read_descriptor(1, cluster_aabb_buf)
+            .access_descriptor(2, light_index_buf, AccessType::ComputeShaderReadWrite)
+            .write_descriptor(3, light_grid_buf)
+            .record_compute(move |compute, _| {
+                compute.push_constants(&push_consts).dispatch_3d(
+                    cluster_count.x,
+                    cluster_count.y,
+                    cluster_count.z,
+                );
+            });
+    }
+}
@@ -0,0 +1,67 @@
+use {
+    bytemuck::cast_slice, glam::Vec3, inline_spirv::include_spirv, screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Runtime-adjustable color grading as a single compute pass, a lightweight alternative to
+/// [`Lut3dGrader`](crate::Lut3dGrader).
+///
+/// The source and destination images must be `rgba16f` storage images of the same size. Exposure
+/// is applied first, followed by contrast, lift/gamma/gain and finally saturation.
+pub struct ColorAdjuster {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl ColorAdjuster {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/adjust_color.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `src_image`, adjusts each texel and writes `dst_image`.
+    ///
+    /// `exposure` is in stops, where `0.0` leaves brightness unchanged. `saturation` and
+    /// `contrast` are factors, where `1.0` leaves the image unchanged. The identity for `lift` is
+    /// zero and for `gamma` and `gain` is one, per channel.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        exposure: f32,
+        saturation: f32,
+        contrast: f32,
+        lift: Vec3,
+        gamma: Vec3,
+        gain: Vec3,
+    ) {
+        let src_image = src_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        // Each vec3 is aligned to 16 bytes, hence the padding
+        let push_consts = [
+            exposure, saturation, contrast, 0.0, lift.x, lift.y, lift.z, 0.0, gamma.x, gamma.y,
+            gamma.z, 0.0, gain.x, gain.y, gain.z,
+        ];
+
+        render_graph
+            .begin_pass("adjust color")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, src_image)
+            .write_descriptor(1, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&push_consts))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,69 @@
+use {
+    bytemuck::cast_slice,
+    glam::{Mat4, Vec3},
+    inline_spirv::include_spirv,
+    screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Screen-space contact shadows, ray-marched through the depth buffer as a single compute pass.
+///
+/// Each pixel marches a short distance toward the light and is shadowed if the ray passes behind
+/// a surface in the depth image. The depth image must be an `r32f` storage image of linear view
+/// depth and the shadow image an `r8` storage image of the same size, where `1.0` is lit and
+/// `0.0` is shadowed. View space looks down negative z.
+pub struct ContactShadows {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl ContactShadows {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/contact_shadows.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `depth_image` and writes `shadow_image`.
+    ///
+    /// `light_dir` points toward the light in view space and `max_distance` is the view space
+    /// length of each ray, which is split into `step_count` steps. `proj` is the projection used
+    /// to render the depth image and transforms view space into clip space.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        depth_image: impl Into<AnyImageNode>,
+        shadow_image: impl Into<AnyImageNode>,
+        light_dir: Vec3,
+        max_distance: f32,
+        step_count: u32,
+        proj: Mat4,
+    ) {
+        let depth_image = depth_image.into();
+        let shadow_image = shadow_image.into();
+        let shadow_info = render_graph.node_info(shadow_image);
+
+        let mut push_consts = Vec::with_capacity(84);
+        push_consts.extend_from_slice(cast_slice(&proj.to_cols_array()));
+        push_consts.extend_from_slice(cast_slice(&light_dir.to_array()));
+        push_consts.extend_from_slice(&max_distance.to_ne_bytes());
+        push_consts.extend_from_slice(&step_count.to_ne_bytes());
+
+        render_graph
+            .begin_pass("contact shadows")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, depth_image)
+            .write_descriptor(1, shadow_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(shadow_info.width, shadow_info.height);
+            });
+    }
+}
@@ -0,0 +1,73 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Describes how [`DepthDownsampler`] combines the source texels covered by each destination
+/// texel.
+#[derive(Clone, Copy, Debug)]
+pub enum DepthReduction {
+    /// The smallest depth, which is the farthest surface when using reversed depth.
+    Min,
+    /// The largest depth, which is the nearest surface when using reversed depth.
+    Max,
+    /// The mean depth, for effects which want a smooth result rather than a conservative one.
+    Average,
+}
+
+impl DepthReduction {
+    fn index(self) -> u32 {
+        match self {
+            Self::Min => 0,
+            Self::Max => 1,
+            Self::Average => 2,
+        }
+    }
+}
+
+/// Downsamples a depth image to half resolution as a single compute pass, for building min or
+/// max depth pyramids for screen-space reflections and occlusion culling.
+///
+/// Both images must be `r32f` storage images, with the destination half the size of the source
+/// rounded down. Odd dimensions are handled conservatively: the extra row or column of the source
+/// is folded into the edge texels of the destination. Unlike
+/// [`HizBuilder`](crate::HizBuilder), the source and destination are separate images and the
+/// reduction is selectable.
+pub struct DepthDownsampler {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl DepthDownsampler {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/downsample_depth.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `src_image` and writes the half resolution `dst_image`.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        reduction: DepthReduction,
+    ) {
+        let src_image = src_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        render_graph
+            .begin_pass("downsample depth")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, src_image)
+            .write_descriptor(1, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[reduction.index()]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,56 @@
+use {
+    bytemuck::cast_slice, glam::Mat4, inline_spirv::include_spirv, screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Reconstructs world-space positions from depth once per frame, as a single compute pass.
+///
+/// The depth image must be an `r32f` storage image of linear view depth and the position image an
+/// `rgba32f` storage image of the same size. Each texel of the position image holds the world
+/// position in `xyz` and `1.0` in `w`. Passes such as [`Ssao`](crate::Ssao),
+/// [`SsrTrace`](crate::SsrTrace) and [`Fog`](crate::Fog) reconstruct positions themselves; the
+/// output of this pass is intended for custom passes which would otherwise repeat that work.
+pub struct DepthToWorldPos {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl DepthToWorldPos {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/depth_to_world_pos.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `depth_image` and writes `position_image`.
+    ///
+    /// `inv_view_proj` is the inverse of the perspective view-projection matrix used to render the
+    /// depth image.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        depth_image: impl Into<AnyImageNode>,
+        position_image: impl Into<AnyImageNode>,
+        inv_view_proj: Mat4,
+    ) {
+        let depth_image = depth_image.into();
+        let position_image = position_image.into();
+        let position_info = render_graph.node_info(position_image);
+
+        render_graph
+            .begin_pass("depth to world pos")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, depth_image)
+            .write_descriptor(1, position_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&inv_view_proj.to_cols_array()))
+                    .dispatch_2d(position_info.width, position_info.height);
+            });
+    }
+}
@@ -0,0 +1,59 @@
+use {inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Depth of field with polygonal (aperture blade) or circular bokeh.
+///
+/// The color source and destination are `rgba16f` storage images and the circle of confusion is
+/// an `r16f` storage image where the absolute value is the blur radius relative to `max_radius`.
+pub struct DofBokeh {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl DofBokeh {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/dof_bokeh.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a depth of field pass which reads `color_image` and `coc_image` and writes
+    /// `dst_image`.
+    ///
+    /// `max_radius` is the largest blur radius in texels and `blade_count` is the number of
+    /// aperture blades; fewer than three blades produces circular bokeh.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        color_image: impl Into<AnyImageNode>,
+        coc_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        max_radius: f32,
+        blade_count: u32,
+    ) {
+        let color_image = color_image.into();
+        let coc_image = coc_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        let mut push_consts = Vec::with_capacity(8);
+        push_consts.extend_from_slice(&max_radius.to_ne_bytes());
+        push_consts.extend_from_slice(&blade_count.to_ne_bytes());
+
+        render_graph
+            .begin_pass("dof bokeh")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, color_image)
+            .read_descriptor(1, coc_image)
+            .write_descriptor(2, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,59 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Depth of field gather which weights samples by luminance so bright highlights keep their
+/// energy when defocused.
+///
+/// This is a higher quality alternative to [`DofBokeh`](crate::DofBokeh) for scenes with strong
+/// highlights. The color source and destination are `rgba16f` storage images, the circle of
+/// confusion is an `r16f` storage image where the absolute value is the blur radius relative to
+/// `max_radius`, and luminance is an `r16f` storage image.
+pub struct DofGatherWeighted {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl DofGatherWeighted {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/dof_gather_weighted.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a depth of field pass which reads `color_image`, `coc_image` and `luminance_image`
+    /// and writes `dst_image`.
+    ///
+    /// `max_radius` is the largest blur radius in texels.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        color_image: impl Into<AnyImageNode>,
+        coc_image: impl Into<AnyImageNode>,
+        luminance_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        max_radius: f32,
+    ) {
+        let color_image = color_image.into();
+        let coc_image = coc_image.into();
+        let luminance_image = luminance_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        render_graph
+            .begin_pass("dof gather weighted")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, color_image)
+            .read_descriptor(1, coc_image)
+            .read_descriptor(2, luminance_image)
+            .write_descriptor(3, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[max_radius]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
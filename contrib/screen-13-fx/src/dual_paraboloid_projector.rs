@@ -0,0 +1,67 @@
+use {
+    bytemuck::cast_slice, glam::Mat4, inline_spirv::include_spirv, screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Reprojects an omnidirectional shadow map into a dual-paraboloid shadow map, as a single
+/// compute pass.
+///
+/// The source image holds the linear distance from the light in the six faces of a cube, ordered
+/// `+x`, `-x`, `+y`, `-y`, `+z` and `-z`, and must be an `r32f` storage image with six array
+/// layers. The destination must be an `r32f` storage image with two array layers, which receive
+/// the hemispheres facing `+z` and `-z` of light space as normalized depth. Texels outside of the
+/// paraboloid disk are written as `1.0`.
+pub struct DualParaboloidProjector {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl DualParaboloidProjector {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/dual_paraboloid_project.comp", comp)
+                        .as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `cube_depth_image` and writes both hemispheres of `dst_image`.
+    ///
+    /// `light_rotation` orients the paraboloids by transforming light space directions into the
+    /// space of the cube faces. Distances are normalized between `near` and `far`.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        cube_depth_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        light_rotation: Mat4,
+        near: f32,
+        far: f32,
+    ) {
+        let cube_depth_image = cube_depth_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        let mut push_consts = Vec::with_capacity(72);
+        push_consts.extend_from_slice(cast_slice(&light_rotation.to_cols_array()));
+        push_consts.extend_from_slice(&near.to_ne_bytes());
+        push_consts.extend_from_slice(&far.to_ne_bytes());
+
+        render_graph
+            .begin_pass("dual paraboloid project")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, cube_depth_image)
+            .write_descriptor(1, dst_image)
+            .record_compute(move |compute, _| {
+                compute.push_constants(&push_consts).dispatch_3d(
+                    dst_info.width,
+                    dst_info.height,
+                    2,
+                );
+            });
+    }
+}
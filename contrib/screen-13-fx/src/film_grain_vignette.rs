@@ -0,0 +1,67 @@
+use {inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Film grain and vignette stylization as a single compute pass.
+///
+/// The source and destination images must be `rgba8` storage images of the same size. Grain is
+/// taken from an `r8` storage image of noise, such as the output of
+/// [`BlueNoiseGenerator`](crate::BlueNoiseGenerator), which is tiled across the destination and
+/// offset each frame.
+pub struct FilmGrainVignette {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl FilmGrainVignette {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/film_grain_vignette.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `src_image` and `noise_image` and writes `dst_image`.
+    ///
+    /// `grain_intensity` is the strength of the grain (`0.1` is subtle). The vignette darkens
+    /// texels which are further than `vignette_radius` from the center, where `0.5` is half the
+    /// image height, fading to black over `vignette_softness`. `frame_index` should increase each
+    /// frame so that the grain changes over time.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        noise_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        grain_intensity: f32,
+        vignette_radius: f32,
+        vignette_softness: f32,
+        frame_index: u32,
+    ) {
+        let src_image = src_image.into();
+        let noise_image = noise_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        let mut push_consts = Vec::with_capacity(16);
+        push_consts.extend_from_slice(&grain_intensity.to_ne_bytes());
+        push_consts.extend_from_slice(&vignette_radius.to_ne_bytes());
+        push_consts.extend_from_slice(&vignette_softness.to_ne_bytes());
+        push_consts.extend_from_slice(&frame_index.to_ne_bytes());
+
+        render_graph
+            .begin_pass("film grain vignette")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, src_image)
+            .read_descriptor(1, noise_image)
+            .write_descriptor(2, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,78 @@
+use {
+    bytemuck::cast_slice,
+    glam::{Mat4, Vec3},
+    inline_spirv::include_spirv,
+    screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Exponential height fog applied as a post-process, as a single compute pass.
+///
+/// Fog density decreases exponentially with world-space height and is integrated along each view
+/// ray, so distant and low-lying surfaces are fogged the most. The color and destination images
+/// must be `rgba16f` storage images and the depth image an `r32f` storage image of linear view
+/// depth, all of the same size. View space looks down negative z with positive y up.
+pub struct Fog {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl Fog {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/apply_fog.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `color_image` and `depth_image` and writes the fogged
+    /// `dst_image`.
+    ///
+    /// `density` is the fog density at a height of zero and `height_falloff` controls how quickly
+    /// it thins out with height. `inv_view` transforms view space into world space, and
+    /// `fov_y` (in radians) and `aspect_ratio` describe the projection used to render the depth
+    /// image.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        color_image: impl Into<AnyImageNode>,
+        depth_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        fog_color: Vec3,
+        density: f32,
+        height_falloff: f32,
+        inv_view: Mat4,
+        fov_y: f32,
+        aspect_ratio: f32,
+    ) {
+        let color_image = color_image.into();
+        let depth_image = depth_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        let mut push_consts = Vec::with_capacity(92);
+        push_consts.extend_from_slice(cast_slice(&inv_view.to_cols_array()));
+        push_consts.extend_from_slice(cast_slice(&fog_color.to_array()));
+        push_consts.extend_from_slice(&density.to_ne_bytes());
+        push_consts.extend_from_slice(&height_falloff.to_ne_bytes());
+        push_consts.extend_from_slice(&(fov_y * 0.5).tan().to_ne_bytes());
+        push_consts.extend_from_slice(&aspect_ratio.to_ne_bytes());
+
+        render_graph
+            .begin_pass("apply fog")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, color_image)
+            .read_descriptor(1, depth_image)
+            .write_descriptor(2, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
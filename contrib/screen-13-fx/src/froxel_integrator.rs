@@ -0,0 +1,61 @@
+use {inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Integrates a froxel (frustum voxel) volume of in-scattering and extinction front-to-back along
+/// the view direction.
+///
+/// The source volume is an `rgba16f` 3D storage image with in-scattered light in the RGB channels
+/// and extinction in the alpha channel. The destination volume is an `rgba16f` 3D storage image of
+/// the same size which holds the accumulated in-scattering in the RGB channels and the
+/// transmittance from the camera in the alpha channel.
+pub struct FroxelIntegrator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl FroxelIntegrator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/integrate_froxels.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records an integration pass which reads `scattering_image` and writes `integrated_image`.
+    ///
+    /// Each depth slice of the volume covers an exponentially-distributed range of view depth
+    /// between `near` and `far`.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        scattering_image: impl Into<AnyImageNode>,
+        integrated_image: impl Into<AnyImageNode>,
+        near: f32,
+        far: f32,
+    ) {
+        let scattering_image = scattering_image.into();
+        let integrated_image = integrated_image.into();
+        let integrated_info = render_graph.node_info(integrated_image);
+
+        debug_assert_eq!(integrated_info.ty, vk::ImageType::TYPE_3D);
+
+        let mut push_consts = Vec::with_capacity(12);
+        push_consts.extend_from_slice(&integrated_info.depth.to_ne_bytes());
+        push_consts.extend_from_slice(&near.to_ne_bytes());
+        push_consts.extend_from_slice(&far.to_ne_bytes());
+
+        render_graph
+            .begin_pass("integrate froxels")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, scattering_image)
+            .write_descriptor(1, integrated_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(integrated_info.width, integrated_info.height);
+            });
+    }
+}
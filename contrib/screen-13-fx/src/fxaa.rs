@@ -0,0 +1,67 @@
+use {
+    crate::{apply_effect, ImageEffect},
+    bytemuck::cast_slice,
+    inline_spirv::include_spirv,
+    screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Fast approximate anti-aliasing (FXAA 3.11) as a single compute pass.
+///
+/// The source image is sampled and so must have been created with `SAMPLED` usage, and the
+/// destination image must be an `rgba8` storage image of the same size.
+pub struct Fxaa {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl Fxaa {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/fxaa.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records an anti-aliasing pass which reads `src_image` and writes `dst_image`.
+    ///
+    /// `edge_threshold` is the minimum local contrast, relative to the brightest neighbor, which is
+    /// treated as an edge (`0.125` is a good default). `subpixel_quality` controls how much
+    /// sub-pixel aliasing is removed (`0.75` is a good default, `0.0` disables it).
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        edge_threshold: f32,
+        subpixel_quality: f32,
+    ) {
+        apply_effect(
+            render_graph,
+            self,
+            src_image,
+            dst_image,
+            &(edge_threshold, subpixel_quality),
+        );
+    }
+}
+
+impl ImageEffect for Fxaa {
+    type Params = (f32, f32);
+
+    fn name(&self) -> &'static str {
+        "fxaa"
+    }
+
+    fn pipeline(&self) -> &Arc<ComputePipeline> {
+        &self.pipeline
+    }
+
+    fn push_constants(&self, &(edge_threshold, subpixel_quality): &Self::Params) -> Vec<u8> {
+        cast_slice(&[edge_threshold, subpixel_quality]).to_vec()
+    }
+}
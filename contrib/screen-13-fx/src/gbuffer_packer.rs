@@ -0,0 +1,59 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Packs separate G-buffer images into two compact images for deferred shading.
+///
+/// Inputs are an `rgba8` albedo image, an `rgba16f` image of normals in `xyz` and an `rgba8`
+/// material image holding roughness, metallic and ambient occlusion in `rgb`. The first output is
+/// an `rgba8` image of albedo in `rgb` and metallic in `a`; the second is an `rgba16f` image of
+/// the octahedral-encoded normal in `rg`, roughness in `b` and ambient occlusion in `a`. All images
+/// are storage images of the same size.
+pub struct GbufferPacker {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl GbufferPacker {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/pack_gbuffer.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `albedo_image`, `normal_image` and `material_image` and writes
+    /// `packed0_image` and `packed1_image`.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        albedo_image: impl Into<AnyImageNode>,
+        normal_image: impl Into<AnyImageNode>,
+        material_image: impl Into<AnyImageNode>,
+        packed0_image: impl Into<AnyImageNode>,
+        packed1_image: impl Into<AnyImageNode>,
+    ) {
+        let albedo_image = albedo_image.into();
+        let normal_image = normal_image.into();
+        let material_image = material_image.into();
+        let packed0_image = packed0_image.into();
+        let packed1_image = packed1_image.into();
+        let packed0_info = render_graph.node_info(packed0_image);
+
+        render_graph
+            .begin_pass("pack gbuffer")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, albedo_image)
+            .read_descriptor(1, normal_image)
+            .read_descriptor(2, material_image)
+            .write_descriptor(3, packed0_image)
+            .write_descriptor(4, packed1_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[packed0_info.width, packed0_info.height]))
+                    .dispatch_2d(packed0_info.width, packed0_info.height);
+            });
+    }
+}
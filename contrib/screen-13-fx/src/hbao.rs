@@ -0,0 +1,67 @@
+use {
+    bytemuck::cast_slice, glam::Mat4, inline_spirv::include_spirv, screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Horizon-based ambient occlusion computed from depth alone.
+///
+/// The input is an `r32f` storage image of linear view depth and the output is an `r8` storage
+/// image where `1.0` is fully unoccluded. This is heavier than [`crate::Ssao`] but produces
+/// smoother, less noisy occlusion.
+pub struct Hbao {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl Hbao {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/hbao.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records an occlusion pass which reads `depth_image` and writes `occlusion_image`.
+    ///
+    /// `radius` is in view-space units and `angle_bias` (in radians) raises the tangent plane to
+    /// avoid self-occlusion on flat surfaces. Each texel marches `direction_count` directions of
+    /// `step_count` steps each; `projection` is the projection used to render the depth.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        depth_image: impl Into<AnyImageNode>,
+        occlusion_image: impl Into<AnyImageNode>,
+        radius: f32,
+        angle_bias: f32,
+        direction_count: u32,
+        step_count: u32,
+        projection: Mat4,
+    ) {
+        let depth_image = depth_image.into();
+        let occlusion_image = occlusion_image.into();
+        let occlusion_info = render_graph.node_info(occlusion_image);
+
+        let mut push_consts = Vec::with_capacity(80);
+        push_consts.extend_from_slice(cast_slice(&projection.to_cols_array()));
+        push_consts.extend_from_slice(&radius.to_ne_bytes());
+        push_consts.extend_from_slice(&angle_bias.to_ne_bytes());
+        push_consts.extend_from_slice(&direction_count.to_ne_bytes());
+        push_consts.extend_from_slice(&step_count.to_ne_bytes());
+
+        render_graph
+            .begin_pass("hbao")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, depth_image)
+            .write_descriptor(1, occlusion_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(occlusion_info.width, occlusion_info.height);
+            });
+    }
+}
@@ -0,0 +1,58 @@
+use {inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Generates terrain normals from a heightmap, as a single compute pass.
+///
+/// The height image must be an `r32f` storage image and the normal image an `rgba8` storage image
+/// of the same size. Normals point along `+y` with the image `x` and `y` axes mapped onto world
+/// `x` and `z`, and are encoded as `normal * 0.5 + 0.5`.
+pub struct HeightmapNormalGenerator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl HeightmapNormalGenerator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/heightmap_to_normals.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `height_image` and writes `normal_image`.
+    ///
+    /// Height values are multiplied by `height_scale` and `texel_spacing` is the world-space
+    /// distance between adjacent texels.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        height_image: impl Into<AnyImageNode>,
+        normal_image: impl Into<AnyImageNode>,
+        height_scale: f32,
+        texel_spacing: f32,
+    ) {
+        let height_image = height_image.into();
+        let normal_image = normal_image.into();
+        let normal_info = render_graph.node_info(normal_image);
+
+        let mut push_consts = Vec::with_capacity(16);
+        push_consts.extend_from_slice(&normal_info.width.to_ne_bytes());
+        push_consts.extend_from_slice(&normal_info.height.to_ne_bytes());
+        push_consts.extend_from_slice(&height_scale.to_ne_bytes());
+        push_consts.extend_from_slice(&texel_spacing.to_ne_bytes());
+
+        render_graph
+            .begin_pass("heightmap to normals")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, height_image)
+            .write_descriptor(1, normal_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(normal_info.width, normal_info.height);
+            });
+    }
+}
@@ -0,0 +1,67 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Builds a hierarchical depth (Hi-Z) pyramid, where each texel of a mip level holds the maximum
+/// depth of the texels it covers in the level above.
+///
+/// The pyramid image must be an `r32f` storage image with mip level zero already filled with depth.
+/// Odd dimensions are handled conservatively: the extra row or column of a level is folded into
+/// the edge texels of the next level.
+pub struct HizBuilder {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl HizBuilder {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/build_hiz.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which fills every mip level of `hiz_image` after the first, one level at a
+    /// time.
+    pub fn apply(&self, render_graph: &mut RenderGraph, hiz_image: impl Into<AnyImageNode>) {
+        let hiz_image = hiz_image.into();
+        let hiz_info = render_graph.node_info(hiz_image);
+        let mut pass = render_graph
+            .begin_pass("build hi-z")
+            .bind_pipeline(&self.pipeline);
+
+        for mip_level in 1..hiz_info.mip_level_count {
+            let src_width = (hiz_info.width >> (mip_level - 1)).max(1);
+            let src_height = (hiz_info.height >> (mip_level - 1)).max(1);
+            let dst_width = (src_width >> 1).max(1);
+            let dst_height = (src_height >> 1).max(1);
+
+            pass = pass
+                .read_descriptor_as(
+                    0,
+                    hiz_image,
+                    hiz_info
+                        .default_view_info()
+                        .to_builder()
+                        .base_mip_level(mip_level - 1)
+                        .mip_level_count(1),
+                )
+                .write_descriptor_as(
+                    1,
+                    hiz_image,
+                    hiz_info
+                        .default_view_info()
+                        .to_builder()
+                        .base_mip_level(mip_level)
+                        .mip_level_count(1),
+                )
+                .record_compute(move |compute, _| {
+                    compute
+                        .push_constants(cast_slice(&[src_width, src_height]))
+                        .dispatch_2d(dst_width, dst_height);
+                });
+        }
+    }
+}
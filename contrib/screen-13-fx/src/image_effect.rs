@@ -0,0 +1,54 @@
+use {screen_13::prelude::*, std::sync::Arc};
+
+/// A compute pass which reads one image and writes another of the same size, configured only by
+/// push constants.
+///
+/// Implementing this trait allows an effect to be recorded by [`apply_effect`], so that a stack of
+/// effects may be composed uniformly. The pipeline must read the source image at binding `0` and
+/// write the destination image at binding `1`, with one invocation per destination texel.
+///
+/// [`ChromaticAberration`](crate::ChromaticAberration), [`Fxaa`](crate::Fxaa),
+/// [`InverseTonemapper`](crate::InverseTonemapper), [`Kuwahara`](crate::Kuwahara),
+/// [`MotionBlurRadial`](crate::MotionBlurRadial) and
+/// [`PrefilterSharpen`](crate::PrefilterSharpen) implement this trait, with parameters given in
+/// the same order as their `apply` functions.
+pub trait ImageEffect {
+    /// The values which configure a single pass of this effect.
+    type Params;
+
+    /// Returns the name of the pass, as shown by debugging tools.
+    fn name(&self) -> &'static str;
+
+    /// Returns the pipeline which is bound to the pass.
+    fn pipeline(&self) -> &Arc<ComputePipeline>;
+
+    /// Returns the push constant data of a pass configured by `params`.
+    fn push_constants(&self, params: &Self::Params) -> Vec<u8>;
+}
+
+/// Records a pass of `effect` which reads `src_image` and writes `dst_image`.
+pub fn apply_effect<E>(
+    render_graph: &mut RenderGraph,
+    effect: &E,
+    src_image: impl Into<AnyImageNode>,
+    dst_image: impl Into<AnyImageNode>,
+    params: &E::Params,
+) where
+    E: ImageEffect + ?Sized,
+{
+    let src_image = src_image.into();
+    let dst_image = dst_image.into();
+    let dst_info = render_graph.node_info(dst_image);
+    let push_consts = effect.push_constants(params);
+
+    render_graph
+        .begin_pass(effect.name())
+        .bind_pipeline(effect.pipeline())
+        .read_descriptor(0, src_image)
+        .write_descriptor(1, dst_image)
+        .record_compute(move |compute, _| {
+            compute
+                .push_constants(&push_consts)
+                .dispatch_2d(dst_info.width, dst_info.height);
+        });
+}
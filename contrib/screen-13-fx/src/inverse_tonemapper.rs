@@ -0,0 +1,92 @@
+use {
+    crate::{apply_effect, ImageEffect},
+    inline_spirv::include_spirv,
+    screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Describes the tonemapping curve which is inverted by [`InverseTonemapper`].
+#[derive(Clone, Copy, Debug)]
+pub enum TonemapOperator {
+    /// `x / (1 + x)`
+    Reinhard,
+    /// Reinhard with a white point, which is the `max_luminance` given to
+    /// [`InverseTonemapper::apply`].
+    ReinhardExtended,
+    /// The ACES filmic curve fit by Krzysztof Narkowicz.
+    AcesFilmic,
+}
+
+impl TonemapOperator {
+    fn index(self) -> u32 {
+        match self {
+            Self::Reinhard => 0,
+            Self::ReinhardExtended => 1,
+            Self::AcesFilmic => 2,
+        }
+    }
+}
+
+/// Expands low dynamic range content into high dynamic range by inverting a tonemapping curve, as
+/// a single compute pass.
+///
+/// The source image must be an `rgba8` storage image of linear (not sRGB-encoded) values and the
+/// destination image an `rgba16f` storage image of the same size. Each color channel is inverted
+/// separately and alpha is copied unchanged.
+pub struct InverseTonemapper {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl InverseTonemapper {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/inverse_tonemap.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `src_image` and writes the expanded `dst_image`.
+    ///
+    /// The output is clamped to `max_luminance`, which keeps the steep end of each curve from
+    /// producing extreme values for saturated input.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        operator: TonemapOperator,
+        max_luminance: f32,
+    ) {
+        apply_effect(
+            render_graph,
+            self,
+            src_image,
+            dst_image,
+            &(operator, max_luminance),
+        );
+    }
+}
+
+impl ImageEffect for InverseTonemapper {
+    type Params = (TonemapOperator, f32);
+
+    fn name(&self) -> &'static str {
+        "inverse tonemap"
+    }
+
+    fn pipeline(&self) -> &Arc<ComputePipeline> {
+        &self.pipeline
+    }
+
+    fn push_constants(&self, &(operator, max_luminance): &Self::Params) -> Vec<u8> {
+        let mut push_consts = Vec::with_capacity(8);
+        push_consts.extend_from_slice(&max_luminance.to_ne_bytes());
+        push_consts.extend_from_slice(&operator.index().to_ne_bytes());
+        push_consts
+    }
+}
@@ -0,0 +1,96 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Dual-Kawase blur, which reaches large blur radii at a fraction of the cost of a gaussian blur.
+///
+/// A blur is built by chaining several [`KawaseBlur::down`] passes, each writing an image of half
+/// the size of the last, followed by the same number of [`KawaseBlur::up`] passes back to full
+/// size. Source images are sampled and so must have been created with `SAMPLED` usage, and
+/// destination images must be `rgba16f` storage images.
+pub struct KawaseBlur {
+    down_pipeline: Arc<ComputePipeline>,
+    up_pipeline: Arc<ComputePipeline>,
+}
+
+impl KawaseBlur {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            down_pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/kawase_down.comp", comp).as_slice(),
+                ),
+            )?),
+            up_pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/kawase_up.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a downsample pass which reads `src_image` and writes the half-size `dst_image`.
+    ///
+    /// `offset` scales the distance between samples (`1.0` is a good default); larger values widen
+    /// the blur at the cost of artifacts.
+    pub fn down(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        offset: f32,
+    ) {
+        Self::record(
+            &self.down_pipeline,
+            "kawase down",
+            render_graph,
+            src_image.into(),
+            dst_image.into(),
+            offset,
+        );
+    }
+
+    /// Records an upsample pass which reads `src_image` and writes the double-size `dst_image`.
+    ///
+    /// `offset` should match the value given to the corresponding [`KawaseBlur::down`] pass.
+    pub fn up(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        offset: f32,
+    ) {
+        Self::record(
+            &self.up_pipeline,
+            "kawase up",
+            render_graph,
+            src_image.into(),
+            dst_image.into(),
+            offset,
+        );
+    }
+
+    fn record(
+        pipeline: &Arc<ComputePipeline>,
+        name: &str,
+        render_graph: &mut RenderGraph,
+        src_image: AnyImageNode,
+        dst_image: AnyImageNode,
+        offset: f32,
+    ) {
+        let dst_info = render_graph.node_info(dst_image);
+
+        render_graph
+            .begin_pass(name)
+            .bind_pipeline(pipeline)
+            .read_descriptor(0, src_image)
+            .write_descriptor(1, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[offset]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,69 @@
+use {
+    crate::{apply_effect, ImageEffect},
+    bytemuck::cast_slice,
+    inline_spirv::include_spirv,
+    screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Generalized Kuwahara filter for a painterly style, as a single compute pass.
+///
+/// The disk around each texel is split into sectors and the texel takes the mean color of the
+/// sector with the lowest variance, which smooths flat regions while keeping edges. The source
+/// and destination images must be `rgba8` storage images of the same size.
+pub struct Kuwahara {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl Kuwahara {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/kuwahara.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `src_image` and writes the filtered `dst_image`.
+    ///
+    /// `radius` is the radius of the disk in texels; the cost of the pass grows with its square.
+    /// `sector_count` is at most `8`, which is also a good default.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        radius: u32,
+        sector_count: u32,
+    ) {
+        apply_effect(
+            render_graph,
+            self,
+            src_image,
+            dst_image,
+            &(radius, sector_count),
+        );
+    }
+}
+
+impl ImageEffect for Kuwahara {
+    type Params = (u32, u32);
+
+    fn name(&self) -> &'static str {
+        "kuwahara"
+    }
+
+    fn pipeline(&self) -> &Arc<ComputePipeline> {
+        &self.pipeline
+    }
+
+    fn push_constants(&self, &(radius, sector_count): &Self::Params) -> Vec<u8> {
+        debug_assert!(sector_count <= 8);
+
+        cast_slice(&[radius, sector_count]).to_vec()
+    }
+}
@@ -1,18 +1,131 @@
 pub mod prelude {
     pub use super::{
-        BitmapFont, BitmapGlyphColor, ComputePresenter, GraphicPresenter, ImageFormat, ImageLoader,
-        Transition, TransitionPipeline,
+        apply_effect, AtlasPacker, Atmosphere, AtmosphereLuts, AtomicCounterBuffer, Bc7Decoder,
+        BitmapFont, BitmapGlyphColor, BloomCombine, BloomPrefilter, BlueNoiseGenerator,
+        BoundsCalculator, BufferReduction, ChromaticAberration, ClusteredLightCuller,
+        ColorAdjuster, ComputePresenter, ContactShadows, DepthDownsampler, DepthReduction,
+        DepthToWorldPos, DofBokeh, DofGatherWeighted, DualParaboloidProjector, FilmGrainVignette,
+        Fog, FroxelIntegrator, Fxaa, GbufferPacker, GraphicPresenter, Hbao,
+        HeightmapNormalGenerator, HizBuilder, ImageEffect, ImageFormat, ImageLoader,
+        InverseTonemapper, KawaseBlur, Kuwahara, LocalTonemapper, LuminanceAdapter, Lut3dGrader,
+        MotionBlurRadial, MotionBlurVelocity, MotionVectorGenerator, NoiseType,
+        NoiseVolumeGenerator, NormalTangentGenerator, OctahedralNormals, OitAccumulator,
+        OutlineDetector, PoissonKernelGenerator, PrefilterSharpen, SatBuilder, Ssao,
+        SsgiProbeUpdater, SsrTrace, StarfieldGenerator, Taau, TonemapOperator, Transition,
+        TransitionPipeline, VsmBlur, WhiteBalancer,
     };
 }
 
+mod atlas_packer;
+mod atmosphere_luts;
+mod atomic_counter;
+mod bc7_decoder;
 mod bitmap_font;
+mod bloom_combine;
+mod bloom_prefilter;
+mod blue_noise_generator;
+mod bounds_calculator;
+mod buffer_reduction;
+mod chromatic_aberration;
+mod clustered_light_culler;
+mod color_adjuster;
+mod contact_shadows;
+mod depth_downsampler;
+mod depth_to_world_pos;
+mod dof_bokeh;
+mod dof_gather_weighted;
+mod dual_paraboloid_projector;
+mod film_grain_vignette;
+mod fog;
+mod froxel_integrator;
+mod fxaa;
+mod gbuffer_packer;
+mod hbao;
+mod heightmap_normal_generator;
+mod hiz_builder;
+mod image_effect;
 mod image_loader;
+mod inverse_tonemapper;
+mod kawase_blur;
+mod kuwahara;
+mod local_tonemapper;
+mod luminance_adapter;
+mod lut3d_grader;
+mod motion_blur_radial;
+mod motion_blur_velocity;
+mod motion_vector_generator;
+mod noise_volume_generator;
+mod normal_tangent_generator;
+mod octahedral_normals;
+mod oit_accumulator;
+mod outline_detector;
+mod poisson_kernel_generator;
+mod prefilter_sharpen;
 mod presenter;
+mod sat_builder;
+mod ssao;
+mod ssgi_probe_updater;
+mod ssr_trace;
+mod starfield_generator;
+mod taau;
 mod transition;
+mod vsm_blur;
+mod white_balancer;
 
 pub use self::{
+    atlas_packer::AtlasPacker,
+    atmosphere_luts::{Atmosphere, AtmosphereLuts},
+    atomic_counter::AtomicCounterBuffer,
+    bc7_decoder::Bc7Decoder,
     bitmap_font::{BitmapFont, BitmapGlyphColor},
+    bloom_combine::BloomCombine,
+    bloom_prefilter::BloomPrefilter,
+    blue_noise_generator::BlueNoiseGenerator,
+    bounds_calculator::BoundsCalculator,
+    buffer_reduction::BufferReduction,
+    chromatic_aberration::ChromaticAberration,
+    clustered_light_culler::ClusteredLightCuller,
+    color_adjuster::ColorAdjuster,
+    contact_shadows::ContactShadows,
+    depth_downsampler::{DepthDownsampler, DepthReduction},
+    depth_to_world_pos::DepthToWorldPos,
+    dof_bokeh::DofBokeh,
+    dof_gather_weighted::DofGatherWeighted,
+    dual_paraboloid_projector::DualParaboloidProjector,
+    film_grain_vignette::FilmGrainVignette,
+    fog::Fog,
+    froxel_integrator::FroxelIntegrator,
+    fxaa::Fxaa,
+    gbuffer_packer::GbufferPacker,
+    hbao::Hbao,
+    heightmap_normal_generator::HeightmapNormalGenerator,
+    hiz_builder::HizBuilder,
+    image_effect::{apply_effect, ImageEffect},
     image_loader::{ImageFormat, ImageLoader},
+    inverse_tonemapper::{InverseTonemapper, TonemapOperator},
+    kawase_blur::KawaseBlur,
+    kuwahara::Kuwahara,
+    local_tonemapper::LocalTonemapper,
+    luminance_adapter::LuminanceAdapter,
+    lut3d_grader::Lut3dGrader,
+    motion_blur_radial::MotionBlurRadial,
+    motion_blur_velocity::MotionBlurVelocity,
+    motion_vector_generator::MotionVectorGenerator,
+    noise_volume_generator::{NoiseType, NoiseVolumeGenerator},
+    normal_tangent_generator::NormalTangentGenerator,
+    octahedral_normals::OctahedralNormals,
+    oit_accumulator::OitAccumulator,
+    outline_detector::OutlineDetector,
+    poisson_kernel_generator::PoissonKernelGenerator,
+    prefilter_sharpen::PrefilterSharpen,
     presenter::{ComputePresenter, GraphicPresenter},
+    sat_builder::SatBuilder,
+    ssao::Ssao,
+    ssgi_probe_updater::SsgiProbeUpdater,
+    ssr_trace::SsrTrace,
+    starfield_generator::StarfieldGenerator,
+    taau::Taau,
     transition::{Transition, TransitionPipeline},
+    vsm_blur::VsmBlur,
+    white_balancer::WhiteBalancer,
 };
@@ -0,0 +1,59 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Tonemaps high dynamic range content while preserving local detail, as a single compute pass.
+///
+/// The luminance image holds the local average luminance of the source, such as a wide blur of
+/// its luminance, and only that average is compressed by the tonemapping curve; the ratio of each
+/// texel to it is kept. The source image must be an `rgba16f` storage image, the luminance image
+/// an `r16f` storage image and the destination image an `rgba8` storage image, all of the same
+/// size. Alpha is copied unchanged.
+pub struct LocalTonemapper {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl LocalTonemapper {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/local_tonemap.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `src_image` and `luminance_image` and writes the tonemapped
+    /// `dst_image`.
+    ///
+    /// `exposure` scales the source and the local average before tonemapping. `local_contrast`
+    /// scales the detail layer: `1.0` keeps it unchanged, larger values exaggerate it and `0.0`
+    /// removes it.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        luminance_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        exposure: f32,
+        local_contrast: f32,
+    ) {
+        let src_image = src_image.into();
+        let luminance_image = luminance_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        render_graph
+            .begin_pass("local tonemap")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, src_image)
+            .read_descriptor(1, luminance_image)
+            .write_descriptor(2, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[exposure, local_contrast]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,52 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Smooths measured scene luminance over time for use in auto-exposure.
+///
+/// The luminance buffer holds two `f32` values: the average luminance measured this frame,
+/// followed by the adapted luminance, which is updated in place. The adapted value should be
+/// initialized once, for example to the first measured average, and the buffer must have been
+/// created with `STORAGE_BUFFER` usage.
+pub struct LuminanceAdapter {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl LuminanceAdapter {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/adapt_luminance.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which moves the adapted luminance of `luminance_buf` toward the measured
+    /// average.
+    ///
+    /// `delta_time` is the frame time in seconds and `speed` is the adaptation rate per second;
+    /// larger values adapt more quickly.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        luminance_buf: impl Into<AnyBufferNode>,
+        delta_time: f32,
+        speed: f32,
+    ) {
+        let luminance_buf = luminance_buf.into();
+
+        debug_assert!(render_graph.node_info(luminance_buf).size >= 8);
+
+        render_graph
+            .begin_pass("adapt luminance")
+            .bind_pipeline(&self.pipeline)
+            .access_descriptor(0, luminance_buf, AccessType::ComputeShaderReadWrite)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[delta_time, speed]))
+                    .dispatch(1, 1, 1);
+            });
+    }
+}
@@ -0,0 +1,56 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Color grading using a three-dimensional lookup table (LUT) as a single compute pass.
+///
+/// The source and destination images must be `rgba8` storage images of the same size. The LUT is
+/// a 3D image indexed by red, green and blue along its width, height and depth; it is sampled with
+/// linear filtering and so must have been created with `SAMPLED` usage.
+pub struct Lut3dGrader {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl Lut3dGrader {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/apply_lut3d.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a color grading pass which reads `src_image`, looks up each texel in `lut_image`
+    /// and writes `dst_image`.
+    ///
+    /// `strength` blends between the source color (`0.0`) and the fully graded color (`1.0`).
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        lut_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        strength: f32,
+    ) {
+        let src_image = src_image.into();
+        let lut_image = lut_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        debug_assert_eq!(render_graph.node_info(lut_image).ty, vk::ImageType::TYPE_3D);
+
+        render_graph
+            .begin_pass("apply lut3d")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, src_image)
+            .read_descriptor(1, lut_image)
+            .write_descriptor(2, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[strength]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
@@ -0,0 +1,74 @@
+use {
+    crate::{apply_effect, ImageEffect},
+    inline_spirv::include_spirv,
+    screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Radial (zoom) motion blur around a center point, as a single compute pass.
+///
+/// The source and destination images must be `rgba8` storage images of the same size. Unlike
+/// velocity-based motion blur this does not need motion vectors and is intended as a stylization,
+/// such as a sense of speed.
+pub struct MotionBlurRadial {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl MotionBlurRadial {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/motion_blur_radial.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `src_image` and writes `dst_image`.
+    ///
+    /// `center` is the normalized texture coordinate the blur radiates from (usually
+    /// `[0.5, 0.5]`). `strength` is the length of the blur as a fraction of the distance to the
+    /// center (`0.1` is subtle) and `sample_count` is the number of samples taken along it. Samples
+    /// are clamped to the edges of the image.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        center: [f32; 2],
+        strength: f32,
+        sample_count: u32,
+    ) {
+        apply_effect(
+            render_graph,
+            self,
+            src_image,
+            dst_image,
+            &(center, strength, sample_count),
+        );
+    }
+}
+
+impl ImageEffect for MotionBlurRadial {
+    type Params = ([f32; 2], f32, u32);
+
+    fn name(&self) -> &'static str {
+        "motion blur radial"
+    }
+
+    fn pipeline(&self) -> &Arc<ComputePipeline> {
+        &self.pipeline
+    }
+
+    fn push_constants(&self, &(center, strength, sample_count): &Self::Params) -> Vec<u8> {
+        let mut push_consts = Vec::with_capacity(16);
+        push_consts.extend_from_slice(&center[0].to_ne_bytes());
+        push_consts.extend_from_slice(&center[1].to_ne_bytes());
+        push_consts.extend_from_slice(&strength.to_ne_bytes());
+        push_consts.extend_from_slice(&sample_count.to_ne_bytes());
+        push_consts
+    }
+}
@@ -0,0 +1,65 @@
+use {inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Per-pixel motion blur which gathers along motion vectors, as a single compute pass.
+///
+/// The color source and destination are `rgba16f` storage images, the motion image is an `rg16f`
+/// storage image of texture coordinate offsets as written by
+/// [`MotionVectorGenerator`](crate::MotionVectorGenerator), and the depth image is an `r32f`
+/// storage image of linear view depth, all of the same size. Depth is used to stop the background
+/// from bleeding over moving objects which are closer to the camera.
+pub struct MotionBlurVelocity {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl MotionBlurVelocity {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/motion_blur_velocity.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `color_image`, `motion_image` and `depth_image` and writes
+    /// `dst_image`.
+    ///
+    /// `sample_count` is the number of samples taken along each motion vector and
+    /// `max_velocity` is the longest blur, in texels; longer motion vectors are shortened.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        color_image: impl Into<AnyImageNode>,
+        motion_image: impl Into<AnyImageNode>,
+        depth_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        sample_count: u32,
+        max_velocity: f32,
+    ) {
+        let color_image = color_image.into();
+        let motion_image = motion_image.into();
+        let depth_image = depth_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        let mut push_consts = Vec::with_capacity(8);
+        push_consts.extend_from_slice(&sample_count.to_ne_bytes());
+        push_consts.extend_from_slice(&max_velocity.to_ne_bytes());
+
+        render_graph
+            .begin_pass("motion blur velocity")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, color_image)
+            .read_descriptor(1, motion_image)
+            .read_descriptor(2, depth_image)
+            .write_descriptor(3, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
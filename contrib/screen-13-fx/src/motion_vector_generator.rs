@@ -0,0 +1,58 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Computes screen-space motion vectors caused by camera movement, for use in temporal
+/// anti-aliasing and motion blur.
+///
+/// The depth image must be an `r32f` storage image and the motion image an `rg16f` storage image
+/// of the same size. The matrices buffer is a storage buffer holding two column-major `mat4`
+/// values: the current view-projection followed by the previous frame's view-projection. Each
+/// motion vector is the current texture coordinate minus the reprojected previous one.
+///
+/// Only camera motion is captured: moving objects need their own previous transforms, which are
+/// usually written as motion vectors during rasterization.
+pub struct MotionVectorGenerator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl MotionVectorGenerator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/compute_motion_vectors.comp", comp)
+                        .as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `depth_image` and `matrices_buf` and writes `motion_image`.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        depth_image: impl Into<AnyImageNode>,
+        matrices_buf: impl Into<AnyBufferNode>,
+        motion_image: impl Into<AnyImageNode>,
+    ) {
+        let depth_image = depth_image.into();
+        let matrices_buf = matrices_buf.into();
+        let motion_image = motion_image.into();
+        let motion_info = render_graph.node_info(motion_image);
+
+        debug_assert!(render_graph.node_info(matrices_buf).size >= 128);
+
+        render_graph
+            .begin_pass("compute motion vectors")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, depth_image)
+            .read_descriptor(1, matrices_buf)
+            .write_descriptor(2, motion_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[motion_info.width, motion_info.height]))
+                    .dispatch_2d(motion_info.width, motion_info.height);
+            });
+    }
+}
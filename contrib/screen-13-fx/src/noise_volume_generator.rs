@@ -0,0 +1,75 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Describes the kind of noise written by [`NoiseVolumeGenerator`].
+#[derive(Clone, Copy, Debug)]
+pub enum NoiseType {
+    /// Gradient noise with a single octave.
+    Perlin,
+    /// Inverted cellular noise with one feature point per cell, bright at the feature points.
+    Worley,
+    /// Fractal Brownian motion: octaves of Perlin noise, each at twice the frequency and half the
+    /// amplitude of the previous one.
+    Fbm,
+}
+
+impl NoiseType {
+    fn index(self) -> u32 {
+        match self {
+            Self::Perlin => 0,
+            Self::Worley => 1,
+            Self::Fbm => 2,
+        }
+    }
+}
+
+/// Generates a tileable 3D noise texture, suitable for volumetric clouds and fog, as a single
+/// compute pass.
+///
+/// The destination image must be a 3D `r8` storage image. Values are in `[0, 1]` and the noise
+/// repeats seamlessly along every axis.
+pub struct NoiseVolumeGenerator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl NoiseVolumeGenerator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/generate_noise_volume.comp", comp)
+                        .as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which fills `dst_image` with noise.
+    ///
+    /// `frequency` is the number of lattice cells across the volume along each axis. `octaves` is
+    /// only used by [`NoiseType::Fbm`]. Each `seed` value produces a different, but repeatable,
+    /// noise pattern.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        dst_image: impl Into<AnyImageNode>,
+        noise_type: NoiseType,
+        frequency: u32,
+        octaves: u32,
+        seed: u32,
+    ) {
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        render_graph
+            .begin_pass("generate noise volume")
+            .bind_pipeline(&self.pipeline)
+            .write_descriptor(0, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[noise_type.index(), frequency, octaves, seed]))
+                    .dispatch_3d(dst_info.width, dst_info.height, dst_info.depth);
+            });
+    }
+}
@@ -0,0 +1,74 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Generates smooth per-vertex normals and tangents for indexed triangle meshes.
+///
+/// Positions are read as tightly packed `[f32; 3]` values, UVs as `[f32; 2]` values and indices as
+/// `u32` triangle lists. Normals are written as `[f32; 3]` values and tangents as `[f32; 4]`
+/// values, where `w` is the bitangent sign.
+///
+/// Generation takes two passes: the first atomically adds the face normal and tangent of every
+/// triangle into the output buffers as fixed-point integers, and the second normalizes each vertex
+/// in place. The output buffers are cleared to zero first so they must allow `TRANSFER_DST` usage.
+pub struct NormalTangentGenerator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl NormalTangentGenerator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/compute_normals_tangents.comp", comp)
+                        .as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records the passes which fill `normal_buf` and `tangent_buf`.
+    ///
+    /// The vertex count is taken from the size of `normal_buf` and the triangle count from the size
+    /// of `index_buf`.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        position_buf: impl Into<AnyBufferNode>,
+        index_buf: impl Into<AnyBufferNode>,
+        uv_buf: impl Into<AnyBufferNode>,
+        normal_buf: impl Into<AnyBufferNode>,
+        tangent_buf: impl Into<AnyBufferNode>,
+    ) {
+        let position_buf = position_buf.into();
+        let index_buf = index_buf.into();
+        let uv_buf = uv_buf.into();
+        let normal_buf = normal_buf.into();
+        let tangent_buf = tangent_buf.into();
+        let vertex_count = (render_graph.node_info(normal_buf).size / 12) as u32;
+        let triangle_count = (render_graph.node_info(index_buf).size / 12) as u32;
+
+        render_graph
+            .fill_buffer(normal_buf, 0)
+            .fill_buffer(tangent_buf, 0);
+
+        for (phase, work_count, name) in [
+            (0u32, triangle_count, "accumulate normals and tangents"),
+            (1, vertex_count, "normalize normals and tangents"),
+        ] {
+            render_graph
+                .begin_pass(name)
+                .bind_pipeline(&self.pipeline)
+                .read_descriptor(0, position_buf)
+                .read_descriptor(1, index_buf)
+                .read_descriptor(2, uv_buf)
+                .access_descriptor(3, normal_buf, AccessType::ComputeShaderReadWrite)
+                .access_descriptor(4, tangent_buf, AccessType::ComputeShaderReadWrite)
+                .record_compute(move |compute, _| {
+                    compute
+                        .push_constants(cast_slice(&[vertex_count, triangle_count, phase]))
+                        .dispatch_1d(work_count);
+                });
+        }
+    }
+}
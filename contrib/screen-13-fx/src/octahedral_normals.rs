@@ -0,0 +1,89 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Converts between images of unit normals and compact octahedral-encoded normals.
+///
+/// Octahedral encoding stores a unit vector in two channels with nearly uniform precision over
+/// the whole sphere. Normal images are `rgba16f` storage images with the normal in `xyz` and
+/// encoded images are `rg16f` storage images of the same size.
+pub struct OctahedralNormals {
+    decode_pipeline: Arc<ComputePipeline>,
+    encode_pipeline: Arc<ComputePipeline>,
+}
+
+impl OctahedralNormals {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            decode_pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/decode_normals_oct.comp", comp).as_slice(),
+                ),
+            )?),
+            encode_pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/encode_normals_oct.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads the encoded normals of `encoded_image` and writes them to
+    /// `normal_image`.
+    pub fn decode(
+        &self,
+        render_graph: &mut RenderGraph,
+        encoded_image: impl Into<AnyImageNode>,
+        normal_image: impl Into<AnyImageNode>,
+    ) {
+        Self::record(
+            &self.decode_pipeline,
+            "decode normals (octahedral)",
+            render_graph,
+            encoded_image.into(),
+            normal_image.into(),
+        );
+    }
+
+    /// Records a pass which reads the normals of `normal_image` and writes them to
+    /// `encoded_image`.
+    ///
+    /// Normals do not need to be unit length; they are normalized before encoding.
+    pub fn encode(
+        &self,
+        render_graph: &mut RenderGraph,
+        normal_image: impl Into<AnyImageNode>,
+        encoded_image: impl Into<AnyImageNode>,
+    ) {
+        Self::record(
+            &self.encode_pipeline,
+            "encode normals (octahedral)",
+            render_graph,
+            normal_image.into(),
+            encoded_image.into(),
+        );
+    }
+
+    fn record(
+        pipeline: &Arc<ComputePipeline>,
+        name: &str,
+        render_graph: &mut RenderGraph,
+        src_image: AnyImageNode,
+        dst_image: AnyImageNode,
+    ) {
+        let dst_info = render_graph.node_info(dst_image);
+
+        render_graph
+            .begin_pass(name)
+            .bind_pipeline(pipeline)
+            .read_descriptor(0, src_image)
+            .write_descriptor(1, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[dst_info.width, dst_info.height]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
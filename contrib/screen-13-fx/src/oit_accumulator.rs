@@ -0,0 +1,74 @@
+use {inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Accumulates translucent fragments for weighted blended order-independent transparency, as a
+/// single compute pass.
+///
+/// The fragment buffer holds one 32-byte fragment per element: a linear `vec4` color with alpha,
+/// a `uvec2` texel coordinate, a `float` depth in `0.0..=1.0` and four bytes of padding.
+///
+/// Results are accumulated using atomic addition, which is only portable for 32-bit integer
+/// images, and so are stored in fixed point with 8 fractional bits (divide by `256.0`):
+///
+/// - The accumulation image must be an `r32ui` storage image with four array layers, holding
+///   the weighted sums of premultiplied red, green and blue and of alpha.
+/// - The revealage image must be an `r32ui` storage image holding the sum of `-log2(1 - alpha)`;
+///   revealage is `exp2(-value / 256.0)`.
+///
+/// Both images must support `VK_FORMAT_FEATURE_STORAGE_IMAGE_ATOMIC_BIT` for `R32_UINT`, which
+/// all Vulkan implementations provide, and must be cleared to zero before accumulation.
+pub struct OitAccumulator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl OitAccumulator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/oit_accumulate.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which accumulates `fragment_count` fragments of `fragment_buf` into
+    /// `accum_image` and `revealage_image`.
+    ///
+    /// Each fragment is weighted by
+    /// `clamp(alpha * weight_scale * (1 - depth) ^ depth_exponent, 0.01, weight_scale)`; a scale of
+    /// `3000.0` and exponent of `3.0` are good defaults.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        fragment_buf: impl Into<AnyBufferNode>,
+        accum_image: impl Into<AnyImageNode>,
+        revealage_image: impl Into<AnyImageNode>,
+        fragment_count: u32,
+        weight_scale: f32,
+        depth_exponent: f32,
+    ) {
+        let fragment_buf = fragment_buf.into();
+        let accum_image = accum_image.into();
+        let revealage_image = revealage_image.into();
+
+        let mut push_consts = Vec::with_capacity(12);
+        push_consts.extend_from_slice(&fragment_count.to_ne_bytes());
+        push_consts.extend_from_slice(&weight_scale.to_ne_bytes());
+        push_consts.extend_from_slice(&depth_exponent.to_ne_bytes());
+
+        render_graph
+            .begin_pass("oit accumulate")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, fragment_buf)
+            .access_descriptor(1, accum_image, AccessType::ComputeShaderReadWrite)
+            .access_descriptor(2, revealage_image, AccessType::ComputeShaderReadWrite)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_1d(fragment_count);
+            });
+    }
+}
@@ -0,0 +1,58 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Detects outlines from geometry buffers for cel shading, as a single compute pass.
+///
+/// Unlike edge detection on color, creases and silhouettes are found from discontinuities in
+/// depth and normals, so textures and lighting do not produce outlines. The depth image must be
+/// an `r32f` storage image of linear depth, the normal image an `rgba16f` storage image with
+/// normals in `xyz`, and the outline image an `r8` storage image, all of the same size.
+pub struct OutlineDetector {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl OutlineDetector {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/outline_depth_normal.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `depth_image` and `normal_image` and writes the edge strength,
+    /// from zero to one, to `outline_image`.
+    ///
+    /// `depth_threshold` is the relative change in depth which begins an edge (`0.05` is a good
+    /// default) and `normal_threshold` is the change in normal which does the same (`0.4` is a
+    /// good default). Edges reach full strength at twice their threshold.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        depth_image: impl Into<AnyImageNode>,
+        normal_image: impl Into<AnyImageNode>,
+        outline_image: impl Into<AnyImageNode>,
+        depth_threshold: f32,
+        normal_threshold: f32,
+    ) {
+        let depth_image = depth_image.into();
+        let normal_image = normal_image.into();
+        let outline_image = outline_image.into();
+        let outline_info = render_graph.node_info(outline_image);
+
+        render_graph
+            .begin_pass("outline depth normal")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, depth_image)
+            .read_descriptor(1, normal_image)
+            .write_descriptor(2, outline_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[depth_threshold, normal_threshold]))
+                    .dispatch_2d(outline_info.width, outline_info.height);
+            });
+    }
+}
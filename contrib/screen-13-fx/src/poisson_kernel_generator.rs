@@ -0,0 +1,51 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Generates a Poisson-disk distributed sample kernel, such as those used by SSAO and soft
+/// shadows, as a single compute pass.
+///
+/// Samples are placed within the unit disk using a best-candidate approximation of a Poisson-disk
+/// distribution, which is intended as a one-time precompute. The kernel buffer must have been
+/// created with `STORAGE_BUFFER` usage and holds one `vec2` (eight bytes) per sample.
+pub struct PoissonKernelGenerator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl PoissonKernelGenerator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/generate_poisson_kernel.comp", comp)
+                        .as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which writes `sample_count` samples, at most `256`, to `kernel_buf`.
+    ///
+    /// Each `seed` value produces a different, but repeatable, kernel.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        kernel_buf: impl Into<AnyBufferNode>,
+        sample_count: u32,
+        seed: u32,
+    ) {
+        debug_assert!(sample_count <= 256);
+
+        let kernel_buf = kernel_buf.into();
+
+        render_graph
+            .begin_pass("generate poisson kernel")
+            .bind_pipeline(&self.pipeline)
+            .write_descriptor(0, kernel_buf)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[sample_count, seed]))
+                    .dispatch(1, 1, 1);
+            });
+    }
+}
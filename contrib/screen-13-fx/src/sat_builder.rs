@@ -0,0 +1,52 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Builds a summed-area table (integral image), where each texel holds the sum of every source
+/// texel above and to the left of it, inclusive.
+///
+/// The source and destination images must be `rgba32f` storage images of the same size. The table
+/// is built in two passes: the first sums each row of the source into the destination and the
+/// second sums each column of the destination in place. The render graph places a barrier between
+/// the two passes, so no transpose is needed.
+pub struct SatBuilder {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl SatBuilder {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/build_sat.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records the row and column passes which build the summed-area table of `src_image` into
+    /// `dst_image`.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+    ) {
+        let src_image = src_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        for (axis, line_count) in [(0u32, dst_info.height), (1, dst_info.width)] {
+            render_graph
+                .begin_pass("build sat")
+                .bind_pipeline(&self.pipeline)
+                .read_descriptor(0, src_image)
+                .access_descriptor(1, dst_image, AccessType::ComputeShaderReadWrite)
+                .record_compute(move |compute, _| {
+                    compute
+                        .push_constants(cast_slice(&[dst_info.width, dst_info.height, axis]))
+                        .dispatch_1d(line_count);
+                });
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use {
+    bytemuck::cast_slice, glam::Mat4, inline_spirv::include_spirv, screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Screen-space ambient occlusion using a normal-oriented hemisphere kernel.
+///
+/// Inputs are an `r32f` storage image of linear view depth, an `rgba16f` storage image of
+/// view-space normals, and a storage buffer of `vec4` kernel samples (the `xyz` of each sample is a
+/// point within the unit hemisphere around `+Z`). The output is an `r8` storage image where `1.0`
+/// is fully unoccluded.
+pub struct Ssao {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl Ssao {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/ssao.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records an occlusion pass which reads `depth_image`, `normal_image` and `kernel_buf` and
+    /// writes `occlusion_image`.
+    ///
+    /// Every sample in `kernel_buf` is used. `radius` is in view-space units and `bias` offsets the
+    /// depth comparison to avoid self-occlusion acne.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        depth_image: impl Into<AnyImageNode>,
+        normal_image: impl Into<AnyImageNode>,
+        kernel_buf: impl Into<AnyBufferNode>,
+        occlusion_image: impl Into<AnyImageNode>,
+        radius: f32,
+        bias: f32,
+        projection: Mat4,
+    ) {
+        let depth_image = depth_image.into();
+        let normal_image = normal_image.into();
+        let kernel_buf = kernel_buf.into();
+        let occlusion_image = occlusion_image.into();
+        let occlusion_info = render_graph.node_info(occlusion_image);
+        let kernel_size = (render_graph.node_info(kernel_buf).size / 16) as u32;
+
+        let mut push_consts = Vec::with_capacity(76);
+        push_consts.extend_from_slice(cast_slice(&projection.to_cols_array()));
+        push_consts.extend_from_slice(&radius.to_ne_bytes());
+        push_consts.extend_from_slice(&bias.to_ne_bytes());
+        push_consts.extend_from_slice(&kernel_size.to_ne_bytes());
+
+        render_graph
+            .begin_pass("ssao")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, depth_image)
+            .read_descriptor(1, normal_image)
+            .read_descriptor(2, kernel_buf)
+            .write_descriptor(3, occlusion_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(occlusion_info.width, occlusion_info.height);
+            });
+    }
+}
@@ -0,0 +1,78 @@
+use {
+    bytemuck::cast_slice,
+    glam::{Mat4, UVec3},
+    inline_spirv::include_spirv,
+    screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Updates a coarse grid of irradiance probes from the visible screen for screen-space global
+/// illumination.
+///
+/// The probe buffer is a storage buffer of `grid_dims.x * grid_dims.y * grid_dims.z` probes, each
+/// stored as two `vec4` values: the world-space position in `xyz` and the irradiance in `rgb`.
+/// Positions are read and irradiance is updated in place. Each probe which is on screen gathers
+/// nearby texels of the `rgba16f` color image, weighted using the `r32f` depth image and the
+/// `rgba16f` image of world-space normals; probes which are off screen keep their previous value.
+/// All images are storage images of the same size.
+pub struct SsgiProbeUpdater {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl SsgiProbeUpdater {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/update_ssgi_probes.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which updates the irradiance of every probe in `probe_buf`.
+    ///
+    /// `view_projection` transforms world space into the clip space of the screen images.
+    /// `hysteresis` is the fraction of the previous irradiance which is kept each update (`0.9` is
+    /// a good default); lower values react faster but flicker more.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        color_image: impl Into<AnyImageNode>,
+        depth_image: impl Into<AnyImageNode>,
+        normal_image: impl Into<AnyImageNode>,
+        probe_buf: impl Into<AnyBufferNode>,
+        grid_dims: UVec3,
+        view_projection: Mat4,
+        hysteresis: f32,
+    ) {
+        let color_image = color_image.into();
+        let depth_image = depth_image.into();
+        let normal_image = normal_image.into();
+        let probe_buf = probe_buf.into();
+        let probe_count = grid_dims.x * grid_dims.y * grid_dims.z;
+
+        debug_assert!(render_graph.node_info(probe_buf).size >= probe_count as vk::DeviceSize * 32);
+
+        let mut push_consts = Vec::with_capacity(80);
+        push_consts.extend_from_slice(cast_slice(&view_projection.to_cols_array()));
+        push_consts.extend_from_slice(cast_slice(&grid_dims.to_array()));
+        push_consts.extend_from_slice(&hysteresis.to_ne_bytes());
+
+        render_graph
+            .begin_pass("update ssgi probes")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, color_image)
+            .read_descriptor(1, depth_image)
+            .read_descriptor(2, normal_image)
+            .access_descriptor(3, probe_buf, AccessType::ComputeShaderReadWrite)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_1d(probe_count);
+            });
+    }
+}
@@ -0,0 +1,73 @@
+use {
+    bytemuck::cast_slice, glam::Mat4, inline_spirv::include_spirv, screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+/// Screen-space reflection ray marching.
+///
+/// Inputs are an `r32f` storage image of linear view depth, an `rgba16f` storage image of
+/// view-space normals and an `rgba16f` storage image of scene color. The output is an `rgba16f`
+/// storage image holding the reflected color in RGB and the hit mask in alpha (`0.0` on a miss,
+/// fading towards the screen edges).
+pub struct SsrTrace {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl SsrTrace {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/ssr_trace.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a ray marching pass which reads `depth_image`, `normal_image` and `color_image` and
+    /// writes `reflection_image`.
+    ///
+    /// Rays travel at most `max_distance` view-space units in `max_steps` steps, and a step counts
+    /// as a hit when it is behind the depth buffer by less than `thickness`. `projection` is the
+    /// projection used to render the depth.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        depth_image: impl Into<AnyImageNode>,
+        normal_image: impl Into<AnyImageNode>,
+        color_image: impl Into<AnyImageNode>,
+        reflection_image: impl Into<AnyImageNode>,
+        projection: Mat4,
+        max_steps: u32,
+        thickness: f32,
+        max_distance: f32,
+    ) {
+        let depth_image = depth_image.into();
+        let normal_image = normal_image.into();
+        let color_image = color_image.into();
+        let reflection_image = reflection_image.into();
+        let reflection_info = render_graph.node_info(reflection_image);
+
+        let mut push_consts = Vec::with_capacity(76);
+        push_consts.extend_from_slice(cast_slice(&projection.to_cols_array()));
+        push_consts.extend_from_slice(&max_steps.to_ne_bytes());
+        push_consts.extend_from_slice(&thickness.to_ne_bytes());
+        push_consts.extend_from_slice(&max_distance.to_ne_bytes());
+
+        render_graph
+            .begin_pass("ssr trace")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, depth_image)
+            .read_descriptor(1, normal_image)
+            .read_descriptor(2, color_image)
+            .write_descriptor(3, reflection_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(&push_consts)
+                    .dispatch_2d(reflection_info.width, reflection_info.height);
+            });
+    }
+}
@@ -0,0 +1,60 @@
+use {inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Generates a procedural starfield background as a single compute pass, for space scenes.
+///
+/// The destination image must be an `rgba16f` storage image with six array layers, which receive
+/// the faces of a cube map ordered `+x`, `-x`, `+y`, `-y`, `+z` and `-z`, and square faces. Stars
+/// are placed in directions rather than per face, so the faces meet without seams. Texels away
+/// from any star are black.
+pub struct StarfieldGenerator {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl StarfieldGenerator {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/generate_starfield.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which fills every face of `dst_image` with stars.
+    ///
+    /// `density` is the chance, from `0.0` to `1.0`, that a star is placed in each cell of a grid
+    /// which is about four texels wide. `brightness_exponent` shapes the distribution of star
+    /// brightness: `1.0` gives evenly distributed brightness and larger values make most stars
+    /// faint with a few bright ones. Each `seed` value produces a different, but repeatable, sky.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        dst_image: impl Into<AnyImageNode>,
+        density: f32,
+        brightness_exponent: f32,
+        seed: u32,
+    ) {
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        let mut push_consts = Vec::with_capacity(12);
+        push_consts.extend_from_slice(&density.to_ne_bytes());
+        push_consts.extend_from_slice(&brightness_exponent.to_ne_bytes());
+        push_consts.extend_from_slice(&seed.to_ne_bytes());
+
+        render_graph
+            .begin_pass("generate starfield")
+            .bind_pipeline(&self.pipeline)
+            .write_descriptor(0, dst_image)
+            .record_compute(move |compute, _| {
+                compute.push_constants(&push_consts).dispatch_3d(
+                    dst_info.width,
+                    dst_info.height,
+                    6,
+                );
+            });
+    }
+}
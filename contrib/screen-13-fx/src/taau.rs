@@ -0,0 +1,67 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Temporal upscaling (TAAU) of a jittered low resolution image.
+///
+/// The color image is an `rgba16f` storage image rendered at the low resolution, along with an
+/// `r32f` storage image of depth, where smaller values are closer, and an `rg16f` storage image
+/// of motion vectors as written by [`MotionVectorGenerator`](crate::MotionVectorGenerator). The
+/// history image is the previous output and is sampled, so it must have been created with
+/// `SAMPLED` usage. The destination is an `rgba16f` storage image at the high resolution and
+/// should be copied to the history image for the next frame.
+pub struct Taau {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl Taau {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/taau.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records an upscaling pass which reads `color_image`, `history_image`, `motion_image` and
+    /// `depth_image` and writes `dst_image`.
+    ///
+    /// `scale_factor` is the ratio of the output resolution to the input resolution (`2.0` for
+    /// quarter resolution rendering) and `jitter` is the sub-texel offset, in input texels, which
+    /// was applied to the projection when rendering `color_image`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        color_image: impl Into<AnyImageNode>,
+        history_image: impl Into<AnyImageNode>,
+        motion_image: impl Into<AnyImageNode>,
+        depth_image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        scale_factor: f32,
+        jitter: [f32; 2],
+    ) {
+        let color_image = color_image.into();
+        let history_image = history_image.into();
+        let motion_image = motion_image.into();
+        let depth_image = depth_image.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        render_graph
+            .begin_pass("taau")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, color_image)
+            .read_descriptor(1, history_image)
+            .read_descriptor(2, motion_image)
+            .read_descriptor(3, depth_image)
+            .write_descriptor(4, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[jitter[0], jitter[1], scale_factor]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
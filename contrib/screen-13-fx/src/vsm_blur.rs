@@ -0,0 +1,62 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Separable Gaussian blur of variance shadow map moments, as two compute passes.
+///
+/// The moments image holds depth and depth² and must be an `rg32f` storage image. Both moments
+/// are filtered consistently so that the blurred shadow map remains valid for Chebyshev's
+/// inequality.
+pub struct VsmBlur {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl VsmBlur {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/blur_vsm.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a horizontal pass from `moments_image` into `temp_image`, followed by a vertical
+    /// pass from `temp_image` back into `moments_image`.
+    ///
+    /// `temp_image` must be an `rg32f` storage image the same size as `moments_image`. `radius` is
+    /// the number of texels sampled on either side of each texel.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        moments_image: impl Into<AnyImageNode>,
+        temp_image: impl Into<AnyImageNode>,
+        radius: u32,
+    ) {
+        let moments_image = moments_image.into();
+        let temp_image = temp_image.into();
+        let moments_info = render_graph.node_info(moments_image);
+
+        for (axis, src_image, dst_image) in [
+            (0u32, moments_image, temp_image),
+            (1, temp_image, moments_image),
+        ] {
+            render_graph
+                .begin_pass("blur vsm")
+                .bind_pipeline(&self.pipeline)
+                .read_descriptor(0, src_image)
+                .write_descriptor(1, dst_image)
+                .record_compute(move |compute, _| {
+                    compute
+                        .push_constants(cast_slice(&[
+                            moments_info.width,
+                            moments_info.height,
+                            radius,
+                            axis,
+                        ]))
+                        .dispatch_2d(moments_info.width, moments_info.height);
+                });
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use {bytemuck::cast_slice, inline_spirv::include_spirv, screen_13::prelude::*, std::sync::Arc};
+
+/// Automatic white balance using the gray-world assumption, as a single compute pass.
+///
+/// The average color buffer is a storage buffer holding three `f32` values: the mean linear red,
+/// green and blue of the scene, such as sums made with [`BufferReduction`](crate::BufferReduction)
+/// and divided by the texel count. Each channel is scaled
+/// so that the average becomes the neutral gray of the same luminance. The source and destination
+/// images must be `rgba16f` storage images of the same size. Alpha is copied unchanged.
+pub struct WhiteBalancer {
+    pipeline: Arc<ComputePipeline>,
+}
+
+impl WhiteBalancer {
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            pipeline: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/auto_white_balance.comp", comp).as_slice(),
+                ),
+            )?),
+        })
+    }
+
+    /// Records a pass which reads `src_image` and `average_color_buf` and writes the balanced
+    /// `dst_image`.
+    ///
+    /// `strength` blends between the source colors at `0.0` and a fully neutralized average at
+    /// `1.0`; values below `1.0` keep some of the mood of the lighting.
+    pub fn apply(
+        &self,
+        render_graph: &mut RenderGraph,
+        src_image: impl Into<AnyImageNode>,
+        average_color_buf: impl Into<AnyBufferNode>,
+        dst_image: impl Into<AnyImageNode>,
+        strength: f32,
+    ) {
+        let src_image = src_image.into();
+        let average_color_buf = average_color_buf.into();
+        let dst_image = dst_image.into();
+        let dst_info = render_graph.node_info(dst_image);
+
+        debug_assert!(render_graph.node_info(average_color_buf).size >= 12);
+
+        render_graph
+            .begin_pass("auto white balance")
+            .bind_pipeline(&self.pipeline)
+            .read_descriptor(0, src_image)
+            .read_descriptor(1, average_color_buf)
+            .write_descriptor(2, dst_image)
+            .record_compute(move |compute, _| {
+                compute
+                    .push_constants(cast_slice(&[strength]))
+                    .dispatch_2d(dst_info.width, dst_info.height);
+            });
+    }
+}
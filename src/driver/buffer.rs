@@ -11,6 +11,7 @@ use {
     log::trace,
     log::warn,
     std::{
+        collections::{HashMap, hash_map::Entry},
         fmt::{Debug, Formatter},
         mem::ManuallyDrop,
         ops::{Deref, DerefMut, Range},
@@ -58,6 +59,7 @@ pub struct Buffer {
     accesses: Mutex<BufferAccess>,
     allocation: ManuallyDrop<Allocation>,
     buffer: vk::Buffer,
+    buffer_view_cache: Mutex<HashMap<BufferViewInfo, BufferView>>,
     device: Arc<Device>,
 
     /// Information used to create this object.
@@ -172,6 +174,7 @@ impl Buffer {
             accesses: Mutex::new(BufferAccess::new(info.size)),
             allocation: ManuallyDrop::new(allocation),
             buffer,
+            buffer_view_cache: Mutex::new(Default::default()),
             device,
             info,
             name: None,
@@ -431,6 +434,24 @@ impl Buffer {
 
         &mut this.allocation.mapped_slice_mut().unwrap()[0..this.info.size as usize]
     }
+
+    #[profiling::function]
+    pub(crate) fn view(this: &Self, info: BufferViewInfo) -> Result<vk::BufferView, DriverError> {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut buffer_view_cache = this.buffer_view_cache.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut buffer_view_cache = buffer_view_cache.unwrap();
+
+        Ok(match buffer_view_cache.entry(info) {
+            Entry::Occupied(entry) => entry.get().buffer_view,
+            Entry::Vacant(entry) => {
+                entry
+                    .insert(BufferView::create(&this.device, info, this.buffer)?)
+                    .buffer_view
+            }
+        })
+    }
 }
 
 impl Debug for Buffer {
@@ -458,6 +479,18 @@ impl Drop for Buffer {
             return;
         }
 
+        {
+            profiling::scope!("views");
+
+            #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+            let mut buffer_view_cache = self.buffer_view_cache.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let mut buffer_view_cache = buffer_view_cache.unwrap();
+
+            buffer_view_cache.clear();
+        }
+
         {
             profiling::scope!("deallocate");
 
@@ -834,6 +867,94 @@ impl From<UninitializedFieldError> for BufferInfoBuilderError {
     }
 }
 
+struct BufferView {
+    buffer_view: vk::BufferView,
+    device: Arc<Device>,
+}
+
+impl BufferView {
+    #[profiling::function]
+    fn create(
+        device: &Arc<Device>,
+        info: BufferViewInfo,
+        buffer: vk::Buffer,
+    ) -> Result<Self, DriverError> {
+        let device = Arc::clone(device);
+        let create_info = vk::BufferViewCreateInfo::default()
+            .buffer(buffer)
+            .format(info.fmt)
+            .offset(info.start)
+            .range(info.range());
+
+        let buffer_view =
+            unsafe { device.create_buffer_view(&create_info, None) }.map_err(|err| {
+                warn!("{err}");
+
+                DriverError::Unsupported
+            })?;
+
+        Ok(Self {
+            buffer_view,
+            device,
+        })
+    }
+}
+
+impl Drop for BufferView {
+    #[profiling::function]
+    fn drop(&mut self) {
+        if panicking() {
+            return;
+        }
+
+        unsafe {
+            self.device.destroy_buffer_view(self.buffer_view, None);
+        }
+    }
+}
+
+/// Information used to interpret a range of an existing [`Buffer`] instance as formatted texel
+/// data, such as a `imageBuffer` or `samplerBuffer` shader binding.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BufferViewInfo {
+    /// The format of each texel in the buffer.
+    pub fmt: vk::Format,
+
+    /// The start of range.
+    pub start: vk::DeviceSize,
+
+    /// The non-inclusive end of the range.
+    pub end: vk::DeviceSize,
+}
+
+impl BufferViewInfo {
+    /// Specifies a view of `subresource` using the given format.
+    pub fn new(fmt: vk::Format, subresource: impl Into<BufferSubresourceRange>) -> Self {
+        let BufferSubresourceRange { start, end } = subresource.into();
+
+        Self { fmt, start, end }
+    }
+
+    /// Returns the size of the view in bytes, or `vk::WHOLE_SIZE` if the view extends to the end
+    /// of the buffer.
+    pub(crate) fn range(self) -> vk::DeviceSize {
+        if self.end == vk::WHOLE_SIZE {
+            vk::WHOLE_SIZE
+        } else {
+            self.end - self.start
+        }
+    }
+}
+
+impl From<BufferViewInfo> for BufferSubresourceRange {
+    fn from(info: BufferViewInfo) -> Self {
+        Self {
+            start: info.start,
+            end: info.end,
+        }
+    }
+}
+
 /// Specifies a range of buffer data.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct BufferSubresourceRange {
@@ -1358,4 +1479,36 @@ mod tests {
         assert!(B { start: 15, end: 25 }.intersects(B { start: 10, end: 20 }));
         assert!(!B { start: 20, end: 25 }.intersects(B { start: 10, end: 20 }));
     }
+
+    #[test]
+    pub fn buffer_view_info() {
+        let info = BufferViewInfo::new(vk::Format::R32_SFLOAT, 16..48);
+
+        assert_eq!(info.fmt, vk::Format::R32_SFLOAT);
+        assert_eq!(
+            BufferSubresourceRange::from(info),
+            buffer_subresource_range(16..48)
+        );
+
+        let info = BufferViewInfo::new(vk::Format::R8_UINT, None);
+
+        assert_eq!(info.start, 0);
+        assert_eq!(info.end, vk::WHOLE_SIZE);
+    }
+
+    #[test]
+    pub fn buffer_view_info_range() {
+        assert_eq!(
+            BufferViewInfo::new(vk::Format::R32_SFLOAT, 16..48).range(),
+            32
+        );
+        assert_eq!(
+            BufferViewInfo::new(vk::Format::R32_SFLOAT, None).range(),
+            vk::WHOLE_SIZE
+        );
+        assert_eq!(
+            BufferViewInfo::new(vk::Format::R32_SFLOAT, 16..vk::WHOLE_SIZE).range(),
+            vk::WHOLE_SIZE
+        );
+    }
 }
@@ -2,7 +2,7 @@ use {
     super::{DriverError, device::Device},
     ash::vk,
     log::{error, trace, warn},
-    std::{fmt::Debug, ops::Deref, sync::Arc, thread::panicking},
+    std::{fmt::Debug, ops::Deref, sync::Arc, thread::panicking, time::Duration},
 };
 
 // TODO: Expose command functions so the fence, device, waiting flags do not
@@ -125,6 +125,70 @@ impl CommandBuffer {
 
         Ok(())
     }
+
+    /// Stalls by blocking the current thread until the GPU has executed the previous submission to
+    /// this command buffer, or until `timeout` has elapsed.
+    ///
+    /// Returns `false` if `timeout` elapsed first. The submission is then still pending and may be
+    /// waited on again.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::{sync::Arc, time::Duration};
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::graph::RenderGraph;
+    /// # use screen_13::pool::lazy::LazyPool;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let render_graph = RenderGraph::new();
+    /// // render_graph contains a one-time precompute pass
+    /// let mut cmd_buf = render_graph
+    ///     .resolve()
+    ///     .submit(&mut LazyPool::new(&device), 0, 0)?;
+    ///
+    /// if !cmd_buf.wait_until_executed_timeout(Duration::from_secs(1))? {
+    ///     println!("precompute is taking longer than expected");
+    ///
+    ///     cmd_buf.wait_until_executed()?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[profiling::function]
+    pub fn wait_until_executed_timeout(&mut self, timeout: Duration) -> Result<bool, DriverError> {
+        use std::slice::from_ref;
+
+        if !self.waiting {
+            return Ok(true);
+        }
+
+        let timeout = u64::try_from(timeout.as_nanos()).unwrap_or(u64::MAX);
+
+        match unsafe {
+            self.device
+                .wait_for_fences(from_ref(&self.fence), true, timeout)
+        } {
+            Ok(_) => {
+                self.waiting = false;
+
+                Ok(true)
+            }
+            Err(err) if err == vk::Result::TIMEOUT => Ok(false),
+            Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
+                error!("Device lost");
+
+                Err(DriverError::InvalidData)
+            }
+            Err(err) => {
+                error!("{}", err);
+
+                Err(DriverError::OutOfMemory)
+            }
+        }
+    }
 }
 
 impl Deref for CommandBuffer {
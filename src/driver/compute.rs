@@ -2,14 +2,23 @@
 
 use {
     super::{
-        DriverError,
+        DescriptorPoolInfo, DriverError,
         device::Device,
-        shader::{DescriptorBindingMap, PipelineDescriptorInfo, Shader, align_spriv},
+        shader::{
+            DescriptorBindingMap, PipelineDescriptorInfo, Shader, align_spriv,
+            descriptor_sets_compatible,
+        },
     },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
     log::{trace, warn},
-    std::{ffi::CString, ops::Deref, sync::Arc, thread::panicking},
+    std::{
+        ffi::CString,
+        fmt::{Debug, Formatter},
+        ops::Deref,
+        sync::{Arc, atomic::AtomicBool},
+        thread::panicking,
+    },
 };
 
 /// Smart pointer handle to a [pipeline] object.
@@ -23,26 +32,53 @@ use {
 ///
 /// [pipeline]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkPipeline.html
 /// [deref]: core::ops::Deref
-#[derive(Debug)]
 pub struct ComputePipeline {
     pub(crate) descriptor_bindings: DescriptorBindingMap,
     pub(crate) descriptor_info: PipelineDescriptorInfo,
     device: Arc<Device>,
+
+    /// Set after the first dispatch with too few dimensions has been warned about, so that
+    /// per-frame work does not repeat the warning.
+    pub(crate) dispatch_dims_warned: AtomicBool,
+
     pub(crate) layout: vk::PipelineLayout,
 
     /// Information used to create this object.
     pub info: ComputePipelineInfo,
 
+    pub(crate) local_size: Option<[u32; 3]>,
+
     /// A descriptive name used in debugging messages.
     pub name: Option<String>,
 
     pipeline: vk::Pipeline,
     pub(crate) push_constants: Option<vk::PushConstantRange>,
+    shader: Shader,
 }
 
 impl ComputePipeline {
     /// Creates a new compute pipeline on the given device.
     ///
+    /// Returns [`DriverError::Unsupported`] if the push constants of the shader extend past
+    /// [`device.physical_device.properties_v1_0.limits.max_push_constants_size`](crate::driver::physical_device::Vulkan10Limits::max_push_constants_size),
+    /// which may be as small as 128 bytes.
+    ///
+    /// Returns [`DriverError::Unsupported`] if the shader uses bindless (unbounded) descriptor
+    /// arrays and the device does not support
+    /// [`runtime_descriptor_array`](crate::driver::physical_device::Vulkan12Features::runtime_descriptor_array).
+    ///
+    /// Returns [`DriverError::InvalidData`] if the shader defines an image sampler (see
+    /// [`ShaderBuilder::image_sampler`](super::shader::ShaderBuilder::image_sampler)) for a
+    /// descriptor which is not a sampler or combined image sampler.
+    ///
+    /// Returns [`DriverError::InvalidData`] if
+    /// [`ComputePipelineInfo::descriptor_stage_flags`] does not include `COMPUTE`.
+    ///
+    /// In debug builds a warning is logged if the `shared` variables of the shader exceed
+    /// [`device.physical_device.properties_v1_0.limits.max_compute_shared_memory_size`](crate::driver::physical_device::Vulkan10Limits::max_compute_shared_memory_size),
+    /// which may be as small as 16 KiB, as such pipelines may otherwise fail to be created without
+    /// a clear reason.
+    ///
     /// # Panics
     ///
     /// If shader code is not a multiple of four bytes.
@@ -82,14 +118,87 @@ impl ComputePipeline {
         let info: ComputePipelineInfo = info.into();
         let shader = shader.into();
 
+        if !info
+            .descriptor_stage_flags
+            .contains(vk::ShaderStageFlags::COMPUTE)
+        {
+            warn!(
+                "descriptor stage flags {:?} do not include compute",
+                info.descriptor_stage_flags
+            );
+
+            return Err(DriverError::InvalidData);
+        }
+
+        shader.check_device_support(&device)?;
+
         // Use SPIR-V reflection to get the types and counts of all descriptors
         let mut descriptor_bindings = shader.descriptor_bindings();
-        for (descriptor_info, _) in descriptor_bindings.values_mut() {
+        for (descriptor_info, stage_flags) in descriptor_bindings.values_mut() {
+            *stage_flags |= info.descriptor_stage_flags;
+
             if descriptor_info.binding_count() == 0 {
+                if !device
+                    .physical_device
+                    .features_v1_2
+                    .runtime_descriptor_array
+                {
+                    warn!("bindless descriptors require runtime descriptor array support");
+
+                    return Err(DriverError::Unsupported);
+                }
+
                 descriptor_info.set_binding_count(info.bindless_descriptor_count);
             }
         }
 
+        if let Some(descriptor) = shader.unmatched_image_samplers(&descriptor_bindings).next() {
+            warn!(
+                "image sampler defined for descriptor {}.{} which is not a sampler binding",
+                descriptor.set, descriptor.binding
+            );
+
+            return Err(DriverError::InvalidData);
+        }
+
+        let local_size = shader.local_size();
+
+        #[cfg(debug_assertions)]
+        {
+            let max_shared_memory_size = device
+                .physical_device
+                .properties_v1_0
+                .limits
+                .max_compute_shared_memory_size;
+
+            if let Some(shared_memory_size) = shader
+                .shared_memory_size()
+                .filter(|&shared_memory_size| shared_memory_size > max_shared_memory_size)
+            {
+                warn!(
+                    "shared memory uses at least {shared_memory_size} bytes but the device supports {max_shared_memory_size}"
+                );
+            }
+        }
+
+        let push_constants = shader.push_constant_range();
+        if let Some(push_constants) = &push_constants {
+            let max_push_constants_size = device
+                .physical_device
+                .properties_v1_0
+                .limits
+                .max_push_constants_size;
+            let push_constants_end = push_constants.offset + push_constants.size;
+
+            if push_constants_end > max_push_constants_size {
+                warn!(
+                    "push constants end at {push_constants_end} bytes but the device supports {max_push_constants_size}"
+                );
+
+                return Err(DriverError::Unsupported);
+            }
+        }
+
         let descriptor_info = PipelineDescriptorInfo::create(&device, &descriptor_bindings)?;
         let descriptor_set_layouts = descriptor_info
             .layouts
@@ -126,7 +235,6 @@ impl ComputePipeline {
             let mut layout_info =
                 vk::PipelineLayoutCreateInfo::default().set_layouts(&descriptor_set_layouts);
 
-            let push_constants = shader.push_constant_range();
             if let Some(push_constants) = &push_constants {
                 layout_info = layout_info.push_constant_ranges(from_ref(push_constants));
             }
@@ -159,22 +267,328 @@ impl ComputePipeline {
                 descriptor_bindings,
                 descriptor_info,
                 device,
+                dispatch_dims_warned: AtomicBool::new(false),
                 info,
                 layout,
+                local_size,
                 name: None,
                 pipeline,
                 push_constants,
+                shader,
             })
         }
     }
 
+    /// Binds this pipeline and the given descriptor sets to a command buffer recorded outside of a
+    /// render graph, leaving push constants and dispatches to the caller.
+    ///
+    /// The pipeline is bound at [`vk::PipelineBindPoint::COMPUTE`] and `descriptor_sets[n]` is
+    /// bound to descriptor set `first_set + n`, which corresponds to `layout(set = ...)` in the
+    /// shader. Each descriptor set must have been allocated using the matching layout from
+    /// [`ComputePipeline::descriptor_set_layout`].
+    ///
+    /// # Safety
+    ///
+    /// `cmd_buf` must be a valid command buffer of the device this pipeline was created on and it
+    /// must be in the recording state. The descriptor sets must be valid and fully written.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let my_shader_code = [0u8; 1];
+    /// # let shader = Shader::new_compute(my_shader_code.as_slice());
+    /// # let my_pipeline = ComputePipeline::create(&device, ComputePipelineInfo::default(), shader)?;
+    /// # let my_cmd_buf = vk::CommandBuffer::null();
+    /// # let my_descriptor_sets = [vk::DescriptorSet::null()];
+    /// // my_cmd_buf is in the recording state and was created by some other code
+    /// unsafe {
+    ///     ComputePipeline::bind(&my_pipeline, my_cmd_buf, 0, &my_descriptor_sets);
+    ///     device.cmd_dispatch(my_cmd_buf, 64, 1, 1);
+    ///     device.cmd_dispatch(my_cmd_buf, 32, 1, 1);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub unsafe fn bind(
+        this: &Self,
+        cmd_buf: vk::CommandBuffer,
+        first_set: u32,
+        descriptor_sets: &[vk::DescriptorSet],
+    ) {
+        unsafe {
+            this.device
+                .cmd_bind_pipeline(cmd_buf, vk::PipelineBindPoint::COMPUTE, this.pipeline);
+
+            if !descriptor_sets.is_empty() {
+                this.device.cmd_bind_descriptor_sets(
+                    cmd_buf,
+                    vk::PipelineBindPoint::COMPUTE,
+                    this.layout,
+                    first_set,
+                    descriptor_sets,
+                    &[],
+                );
+            }
+        }
+    }
+
+    /// Returns the descriptor set layout bindings of this pipeline, as discovered through SPIR-V
+    /// reflection.
+    ///
+    /// Each item is a descriptor set index and a binding of that descriptor set layout. Bindings
+    /// are returned in no particular order and do not reference any immutable samplers.
+    ///
+    /// See [`ComputePipeline::descriptor_set_count`] for the number of descriptor set layouts.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let my_shader_code = [0u8; 1];
+    /// # let shader = Shader::new_compute(my_shader_code.as_slice());
+    /// # let my_pipeline = ComputePipeline::create(&device, ComputePipelineInfo::default(), shader)?;
+    /// for (descriptor_set_idx, binding) in ComputePipeline::bindings(&my_pipeline) {
+    ///     println!("{descriptor_set_idx}.{} = {:?}", binding.binding, binding.descriptor_type);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn bindings(
+        this: &Self,
+    ) -> impl ExactSizeIterator<Item = (u32, vk::DescriptorSetLayoutBinding<'static>)> + '_ {
+        this.descriptor_bindings
+            .iter()
+            .map(|(descriptor, (descriptor_info, stage_flags))| {
+                (
+                    descriptor.set,
+                    vk::DescriptorSetLayoutBinding::default()
+                        .binding(descriptor.binding)
+                        .descriptor_count(descriptor_info.binding_count())
+                        .descriptor_type(descriptor_info.descriptor_type())
+                        .stage_flags(*stage_flags),
+                )
+            })
+    }
+
+    /// Returns the number of descriptor set layouts used by this pipeline.
+    ///
+    /// Descriptor set indices range from zero up to, but not including, this value.
+    pub fn descriptor_set_count(this: &Self) -> u32 {
+        this.descriptor_info.layouts.len() as _
+    }
+
+    /// Returns the descriptor set layouts used by this pipeline, ordered by descriptor set index.
+    pub fn descriptor_set_layouts(
+        this: &Self,
+    ) -> impl ExactSizeIterator<Item = vk::DescriptorSetLayout> + '_ {
+        this.descriptor_info
+            .layouts
+            .values()
+            .map(|descriptor_set_layout| **descriptor_set_layout)
+    }
+
+    /// Returns the descriptor set layout for descriptor set `descriptor_set_idx`, or `None` if
+    /// `descriptor_set_idx` is not less than [`ComputePipeline::descriptor_set_count`].
+    pub fn descriptor_set_layout(
+        this: &Self,
+        descriptor_set_idx: u32,
+    ) -> Option<vk::DescriptorSetLayout> {
+        this.descriptor_info
+            .layouts
+            .get(&descriptor_set_idx)
+            .map(|descriptor_set_layout| **descriptor_set_layout)
+    }
+
+    /// Returns the descriptor pool size required to allocate one descriptor set for each
+    /// descriptor set layout of this pipeline.
+    ///
+    /// A render graph reserves this much descriptor pool space for each time a pass binds this
+    /// pipeline, before rounding the counts up so that pools may be reused between passes. The
+    /// sizes of pools actually created are available from [`DescriptorPool::info`].
+    ///
+    /// [`DescriptorPool::info`]: super::DescriptorPool::info
+    pub fn descriptor_pool_info(this: &Self) -> DescriptorPoolInfo {
+        let mut info = DescriptorPoolInfo {
+            max_sets: Self::descriptor_set_count(this),
+            ..Default::default()
+        };
+
+        for pool_size in this.descriptor_info.pool_sizes.values() {
+            for (&descriptor_ty, &descriptor_count) in pool_size {
+                info.add_descriptors(descriptor_ty, descriptor_count);
+            }
+        }
+
+        info
+    }
+
+    /// Returns `true` if the pipeline layouts of this pipeline and `other` are compatible for
+    /// descriptor set `descriptor_set_idx`.
+    ///
+    /// Following the Vulkan [pipeline layout compatibility] rules, this requires that descriptor
+    /// sets zero through `descriptor_set_idx` have identical bindings (types, counts, stages and
+    /// immutable samplers) and that both pipelines have identical push constant ranges. Descriptor
+    /// sets bound while using one pipeline then remain valid for the other, up to and including
+    /// that descriptor set.
+    ///
+    /// [pipeline layout compatibility]: https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#descriptorsets-compatibility
+    pub fn is_compatible_for_set(this: &Self, other: &Self, descriptor_set_idx: u32) -> bool {
+        let push_constants = |pipeline: &Self| {
+            pipeline
+                .push_constants
+                .map(|range| (range.stage_flags, range.offset, range.size))
+        };
+
+        push_constants(this) == push_constants(other)
+            && descriptor_sets_compatible(
+                &this.descriptor_bindings,
+                &other.descriptor_bindings,
+                descriptor_set_idx,
+            )
+    }
+
+    /// Returns the pipeline layout of this pipeline.
+    ///
+    /// The pipeline layout, descriptor set layouts, and push constant range allow this pipeline to
+    /// be used with command buffers recorded outside of a render graph, such as those of an
+    /// existing frame graph implementation. See [`ComputePipeline::bind`] for a simpler way to
+    /// bind this pipeline and its descriptor sets.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let my_shader_code = [0u8; 1];
+    /// # let shader = Shader::new_compute(my_shader_code.as_slice());
+    /// # let my_pipeline = ComputePipeline::create(&device, ComputePipelineInfo::default(), shader)?;
+    /// # let my_cmd_buf = vk::CommandBuffer::null();
+    /// # let my_descriptor_sets = [vk::DescriptorSet::null()];
+    /// // my_cmd_buf is in the recording state and was created by some other code
+    /// unsafe {
+    ///     device.cmd_bind_pipeline(my_cmd_buf, vk::PipelineBindPoint::COMPUTE, *my_pipeline);
+    ///     device.cmd_bind_descriptor_sets(
+    ///         my_cmd_buf,
+    ///         vk::PipelineBindPoint::COMPUTE,
+    ///         ComputePipeline::layout(&my_pipeline),
+    ///         0,
+    ///         &my_descriptor_sets,
+    ///         &[],
+    ///     );
+    ///     device.cmd_dispatch(my_cmd_buf, 1, 1, 1);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn layout(this: &Self) -> vk::PipelineLayout {
+        this.layout
+    }
+
+    /// Returns the workgroup size of this pipeline, as discovered through SPIR-V reflection.
+    ///
+    /// Returns `None` if the shader declared its workgroup size using specialization constants.
+    pub fn local_size(this: &Self) -> Option<[u32; 3]> {
+        this.local_size
+    }
+
+    /// Returns the push constant range of this pipeline, if the shader uses push constants.
+    pub fn push_constant_range(this: &Self) -> Option<vk::PushConstantRange> {
+        this.push_constants
+    }
+
+    /// Creates a new compute pipeline on the given device using the same information and shader
+    /// as an existing pipeline.
+    ///
+    /// This is intended for recovery after [`vk::Result::ERROR_DEVICE_LOST`]: once a replacement
+    /// device has been created the pipelines of the lost device may be recreated without keeping
+    /// their shader code around separately. The debugging name, if any, is kept.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let my_shader_code = [0u8; 1];
+    /// # let shader = Shader::new_compute(my_shader_code.as_slice());
+    /// # let my_pipeline = ComputePipeline::create(&device, ComputePipelineInfo::default(), shader)?;
+    /// let new_device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// let my_pipeline = ComputePipeline::recreate(&my_pipeline, &new_device)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn recreate(this: &Self, device: &Arc<Device>) -> Result<Self, DriverError> {
+        let pipeline = Self::create(device, this.info, this.shader.clone())?;
+
+        Ok(if let Some(name) = &this.name {
+            Self::with_name(pipeline, name.as_str())
+        } else {
+            pipeline
+        })
+    }
+
     /// Sets the debugging name assigned to this pipeline.
+    ///
+    /// This is available in all build profiles. When the device was created with debugging enabled
+    /// the name is also given to the Vulkan pipeline and pipeline layout objects so that it appears
+    /// in tools such as RenderDoc.
     pub fn with_name(mut this: Self, name: impl Into<String>) -> Self {
-        this.name = Some(name.into());
+        let name = name.into();
+
+        if Device::is_debug(&this.device) {
+            Device::set_debug_name(&this.device, this.pipeline, &name);
+            Device::set_debug_name(&this.device, this.layout, &format!("{name} layout"));
+        }
+
+        this.name = Some(name);
         this
     }
 }
 
+impl Debug for ComputePipeline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComputePipeline")
+            .field("name", &self.name)
+            .field("pipeline", &self.pipeline)
+            .field("info", &self.info)
+            .field("descriptor_set_count", &Self::descriptor_set_count(self))
+            .field("descriptor_binding_count", &self.descriptor_bindings.len())
+            .field("local_size", &self.local_size)
+            .field("push_constants", &self.push_constants)
+            .finish()
+    }
+}
+
 impl Deref for ComputePipeline {
     type Target = vk::Pipeline;
 
@@ -232,8 +646,23 @@ pub struct ComputePipelineInfo {
     /// }
     /// # "#, comp);
     /// ```
+    ///
+    /// Individual array elements are bound within a render graph pass using a descriptor which
+    /// includes the array index, such as `(0, [42])` for element `42` of binding `0`. Elements
+    /// which are not bound are left empty when the device supports
+    /// [`descriptor_binding_partially_bound`](crate::driver::physical_device::Vulkan12Features::descriptor_binding_partially_bound).
     #[builder(default = "8192")]
     pub bindless_descriptor_count: u32,
+
+    /// The shader stages which may access the descriptors of this pipeline.
+    ///
+    /// Descriptor set layouts are only compatible when their bindings use the same stage flags.
+    /// Adding stages such as `FRAGMENT` allows descriptor sets to be shared with graphic pipelines
+    /// which bind the same descriptor set layout. Must include `COMPUTE`.
+    ///
+    /// The default is `vk::ShaderStageFlags::COMPUTE`.
+    #[builder(default = "vk::ShaderStageFlags::COMPUTE")]
+    pub descriptor_stage_flags: vk::ShaderStageFlags,
 }
 
 impl ComputePipelineInfo {
@@ -242,6 +671,7 @@ impl ComputePipelineInfo {
     pub fn to_builder(self) -> ComputePipelineInfoBuilder {
         ComputePipelineInfoBuilder {
             bindless_descriptor_count: Some(self.bindless_descriptor_count),
+            descriptor_stage_flags: Some(self.descriptor_stage_flags),
         }
     }
 }
@@ -250,6 +680,7 @@ impl Default for ComputePipelineInfo {
     fn default() -> Self {
         Self {
             bindless_descriptor_count: 8192,
+            descriptor_stage_flags: vk::ShaderStageFlags::COMPUTE,
         }
     }
 }
@@ -307,4 +738,20 @@ mod tests {
 
         assert_eq!(info, builder);
     }
+
+    #[test]
+    pub fn compute_pipeline_info_descriptor_stage_flags() {
+        assert_eq!(
+            Info::default().descriptor_stage_flags,
+            vk::ShaderStageFlags::COMPUTE
+        );
+
+        let stage_flags = vk::ShaderStageFlags::COMPUTE | vk::ShaderStageFlags::FRAGMENT;
+        let info = Builder::default()
+            .descriptor_stage_flags(stage_flags)
+            .build();
+
+        assert_eq!(info.descriptor_stage_flags, stage_flags);
+        assert_eq!(info.to_builder().build(), info);
+    }
 }
@@ -123,6 +123,8 @@ impl DescriptorPool {
             pool_size_count += 1;
         }
 
+        // FREE_DESCRIPTOR_SET is required because each DescriptorSet frees itself when dropped,
+        // which lets a leased pool have individual sets replaced without resetting the whole pool
         let descriptor_pool = unsafe {
             device.create_descriptor_pool(
                 &vk::DescriptorPoolCreateInfo::default()
@@ -233,6 +235,53 @@ pub struct DescriptorPoolInfo {
 }
 
 impl DescriptorPoolInfo {
+    /// Adds `descriptor_count` descriptors of type `descriptor_ty` to this pool size.
+    pub(crate) fn add_descriptors(
+        &mut self,
+        descriptor_ty: vk::DescriptorType,
+        descriptor_count: u32,
+    ) {
+        match descriptor_ty {
+            vk::DescriptorType::ACCELERATION_STRUCTURE_KHR => {
+                self.acceleration_structure_count += descriptor_count;
+            }
+            vk::DescriptorType::COMBINED_IMAGE_SAMPLER => {
+                self.combined_image_sampler_count += descriptor_count;
+            }
+            vk::DescriptorType::INPUT_ATTACHMENT => {
+                self.input_attachment_count += descriptor_count;
+            }
+            vk::DescriptorType::SAMPLED_IMAGE => {
+                self.sampled_image_count += descriptor_count;
+            }
+            vk::DescriptorType::SAMPLER => {
+                self.sampler_count += descriptor_count;
+            }
+            vk::DescriptorType::STORAGE_BUFFER => {
+                self.storage_buffer_count += descriptor_count;
+            }
+            vk::DescriptorType::STORAGE_BUFFER_DYNAMIC => {
+                self.storage_buffer_dynamic_count += descriptor_count;
+            }
+            vk::DescriptorType::STORAGE_IMAGE => {
+                self.storage_image_count += descriptor_count;
+            }
+            vk::DescriptorType::STORAGE_TEXEL_BUFFER => {
+                self.storage_texel_buffer_count += descriptor_count;
+            }
+            vk::DescriptorType::UNIFORM_BUFFER => {
+                self.uniform_buffer_count += descriptor_count;
+            }
+            vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC => {
+                self.uniform_buffer_dynamic_count += descriptor_count;
+            }
+            vk::DescriptorType::UNIFORM_TEXEL_BUFFER => {
+                self.uniform_texel_buffer_count += descriptor_count;
+            }
+            _ => unimplemented!("{descriptor_ty:?}"),
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.acceleration_structure_count
             + self.combined_image_sampler_count
@@ -13,10 +13,10 @@ use {
     raw_window_handle::HasDisplayHandle,
     std::{
         cmp::Ordering,
-        ffi::CStr,
+        ffi::{CStr, CString},
         fmt::{Debug, Formatter},
         iter::{empty, repeat_n},
-        mem::{ManuallyDrop, forget},
+        mem::{ManuallyDrop, forget, transmute},
         ops::Deref,
         thread::panicking,
         time::Instant,
@@ -38,6 +38,10 @@ pub struct Device {
 
     pub(super) allocator: ManuallyDrop<Mutex<Allocator>>,
 
+    conditional_rendering_ext: Option<ext::conditional_rendering::DeviceFn>,
+
+    debug_utils_ext: Option<ext::debug_utils::Device>,
+
     device: ash::Device,
 
     /// Vulkan instance pointer, which includes useful functions.
@@ -75,7 +79,7 @@ impl Device {
     where
         F: FnOnce(vk::DeviceCreateInfo) -> ash::prelude::VkResult<ash::Device>,
     {
-        let mut enabled_ext_names = Vec::with_capacity(6);
+        let mut enabled_ext_names = Vec::with_capacity(8);
 
         if display_window {
             enabled_ext_names.push(khr::swapchain::NAME.as_ptr());
@@ -94,10 +98,21 @@ impl Device {
             enabled_ext_names.push(khr::ray_tracing_pipeline::NAME.as_ptr());
         }
 
+        if physical_device
+            .conditional_rendering_features
+            .conditional_rendering
+        {
+            enabled_ext_names.push(ext::conditional_rendering::NAME.as_ptr());
+        }
+
         if physical_device.index_type_uint8_features.index_type_uint8 {
             enabled_ext_names.push(ext::index_type_uint8::NAME.as_ptr());
         }
 
+        if physical_device.supports_memory_budget {
+            enabled_ext_names.push(ext::memory_budget::NAME.as_ptr());
+        }
+
         let priorities = repeat_n(
             1.0,
             physical_device
@@ -131,6 +146,8 @@ impl Device {
         let mut features_v1_2 = vk::PhysicalDeviceVulkan12Features::default();
         let mut acceleration_structure_features =
             vk::PhysicalDeviceAccelerationStructureFeaturesKHR::default();
+        let mut conditional_rendering_features =
+            vk::PhysicalDeviceConditionalRenderingFeaturesEXT::default();
         let mut index_type_uint8_features = vk::PhysicalDeviceIndexTypeUint8FeaturesEXT::default();
         let mut ray_query_features = vk::PhysicalDeviceRayQueryFeaturesKHR::default();
         let mut ray_trace_features = vk::PhysicalDeviceRayTracingPipelineFeaturesKHR::default();
@@ -154,6 +171,13 @@ impl Device {
             features = features.push_next(&mut index_type_uint8_features);
         }
 
+        if physical_device
+            .conditional_rendering_features
+            .conditional_rendering
+        {
+            features = features.push_next(&mut conditional_rendering_features);
+        }
+
         unsafe { get_physical_device_features2(**physical_device, &mut features) };
 
         let device_create_info = vk::DeviceCreateInfo::default()
@@ -359,6 +383,21 @@ impl Device {
             .ray_trace_features
             .ray_tracing_pipeline
             .then(|| khr::ray_tracing_pipeline::Device::new(&instance, &device));
+        let conditional_rendering_ext = physical_device
+            .conditional_rendering_features
+            .conditional_rendering
+            .then(|| {
+                // ash does not provide a high-level wrapper for this extension, so the function
+                // table is loaded directly
+                ext::conditional_rendering::DeviceFn::load(|name| unsafe {
+                    // SAFETY: `PFN_vkVoidFunction` is an `Option` of a function pointer, which has
+                    // the same size as `*const c_void` and represents `None` as null; the loader
+                    // only casts each address back to the type named by `name`, and the extension
+                    // was enabled on `device` so every address is either valid or null
+                    transmute(instance.get_device_proc_addr(device.handle(), name.as_ptr()))
+                })
+            });
+        let debug_utils_ext = debug.then(|| ext::debug_utils::Device::new(&instance, &device));
 
         let pipeline_cache =
             unsafe { device.create_pipeline_cache(&vk::PipelineCacheCreateInfo::default(), None) }
@@ -371,6 +410,8 @@ impl Device {
         Ok(Self {
             accel_struct_ext,
             allocator: ManuallyDrop::new(Mutex::new(allocator)),
+            conditional_rendering_ext,
+            debug_utils_ext,
             device,
             instance,
             pipeline_cache,
@@ -430,6 +471,144 @@ impl Device {
         &this.instance
     }
 
+    /// Returns `true` if this device was created with debugging enabled.
+    pub(crate) fn is_debug(this: &Self) -> bool {
+        this.debug_utils_ext.is_some()
+    }
+
+    /// Sets the name of a Vulkan object as shown by debugging tools such as RenderDoc.
+    ///
+    /// Does nothing unless the device was created with debugging enabled.
+    pub(crate) fn set_debug_name(this: &Self, handle: impl vk::Handle, name: &str) {
+        let Some(debug_utils_ext) = this.debug_utils_ext.as_ref() else {
+            return;
+        };
+
+        let Ok(name) = CString::new(name) else {
+            warn!("invalid debug name: {name}");
+
+            return;
+        };
+
+        unsafe {
+            if let Err(err) = debug_utils_ext.set_debug_utils_object_name(
+                &vk::DebugUtilsObjectNameInfoEXT::default()
+                    .object_handle(handle)
+                    .object_name(&name),
+            ) {
+                warn!("unable to set debug name: {err}");
+            }
+        }
+    }
+
+    /// Records the commands of `f` into `cmd_buf` within a debug label region named `label`, as
+    /// shown by debugging tools such as RenderDoc.
+    ///
+    /// Only records the commands of `f` unless the device was created with debugging enabled.
+    pub(crate) fn with_debug_label(
+        this: &Self,
+        cmd_buf: vk::CommandBuffer,
+        label: &str,
+        f: impl FnOnce(),
+    ) {
+        let Some(debug_utils_ext) = this.debug_utils_ext.as_ref() else {
+            f();
+
+            return;
+        };
+
+        let Ok(label) = CString::new(label) else {
+            warn!("invalid debug label: {label}");
+
+            f();
+
+            return;
+        };
+
+        unsafe {
+            debug_utils_ext.cmd_begin_debug_utils_label(
+                cmd_buf,
+                &vk::DebugUtilsLabelEXT::default().label_name(&label),
+            );
+        }
+
+        f();
+
+        unsafe {
+            debug_utils_ext.cmd_end_debug_utils_label(cmd_buf);
+        }
+    }
+
+    /// Records the commands of `f` into `cmd_buf` within a conditional rendering block which
+    /// executes them only if the 32-bit value at `offset` of `buffer` is non-zero.
+    ///
+    /// Always records and executes the commands of `f` unless the device supports
+    /// `VK_EXT_conditional_rendering`.
+    pub(crate) fn with_conditional_rendering(
+        this: &Self,
+        cmd_buf: vk::CommandBuffer,
+        buffer: vk::Buffer,
+        offset: vk::DeviceSize,
+        f: impl FnOnce(),
+    ) {
+        let Some(conditional_rendering_ext) = this.conditional_rendering_ext.as_ref() else {
+            f();
+
+            return;
+        };
+
+        unsafe {
+            (conditional_rendering_ext.cmd_begin_conditional_rendering_ext)(
+                cmd_buf,
+                &vk::ConditionalRenderingBeginInfoEXT::default()
+                    .buffer(buffer)
+                    .offset(offset),
+            );
+        }
+
+        f();
+
+        unsafe {
+            (conditional_rendering_ext.cmd_end_conditional_rendering_ext)(cmd_buf);
+        }
+    }
+
+    /// Queries the current memory budget and usage of each memory heap of this device.
+    ///
+    /// Budgets change over time as other processes allocate memory, so the result should be
+    /// queried again before it is used to make allocation decisions.
+    ///
+    /// A result of `None` indicates the device does not support `VK_EXT_memory_budget`.
+    #[profiling::function]
+    pub fn memory_budget(this: &Self) -> Option<MemoryBudget> {
+        if !this.physical_device.supports_memory_budget {
+            return None;
+        }
+
+        let mut budget_properties = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let mut properties =
+            vk::PhysicalDeviceMemoryProperties2::default().push_next(&mut budget_properties);
+
+        unsafe {
+            this.instance
+                .get_physical_device_memory_properties2(*this.physical_device, &mut properties);
+        }
+
+        let memory_properties = properties.memory_properties;
+        let heaps = memory_properties.memory_heaps[..memory_properties.memory_heap_count as usize]
+            .iter()
+            .enumerate()
+            .map(|(idx, heap)| MemoryHeapBudget {
+                budget: budget_properties.heap_budget[idx],
+                flags: heap.flags,
+                size: heap.size,
+                usage: budget_properties.heap_usage[idx],
+            })
+            .collect();
+
+        Some(MemoryBudget { heaps })
+    }
+
     pub(crate) fn pipeline_cache(this: &Self) -> vk::PipelineCache {
         this.pipeline_cache
     }
@@ -526,6 +705,50 @@ impl Drop for Device {
     }
 }
 
+/// Memory budget and usage of each memory heap of a device, as returned by
+/// [`Device::memory_budget`].
+#[derive(Clone, Debug)]
+pub struct MemoryBudget {
+    /// The memory heaps of the device, in the order of
+    /// [`PhysicalDevice::memory_properties`].
+    pub heaps: Box<[MemoryHeapBudget]>,
+}
+
+impl MemoryBudget {
+    /// Returns the total budget of all device-local heaps, in bytes.
+    pub fn device_local_budget(&self) -> vk::DeviceSize {
+        self.device_local_heaps().map(|heap| heap.budget).sum()
+    }
+
+    /// Returns the total usage of all device-local heaps, in bytes.
+    pub fn device_local_usage(&self) -> vk::DeviceSize {
+        self.device_local_heaps().map(|heap| heap.usage).sum()
+    }
+
+    fn device_local_heaps(&self) -> impl Iterator<Item = &MemoryHeapBudget> {
+        self.heaps
+            .iter()
+            .filter(|heap| heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL))
+    }
+}
+
+/// Memory budget and usage of a single memory heap.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryHeapBudget {
+    /// An estimate of how much memory this process can allocate from the heap before allocations
+    /// may fail or cause performance degradation, in bytes.
+    pub budget: vk::DeviceSize,
+
+    /// Attribute flags of the heap.
+    pub flags: vk::MemoryHeapFlags,
+
+    /// Total size of the heap, in bytes.
+    pub size: vk::DeviceSize,
+
+    /// An estimate of how much memory this process currently uses in the heap, in bytes.
+    pub usage: vk::DeviceSize,
+}
+
 /// Information used to create a [`Device`] instance.
 #[derive(Builder)]
 #[builder(
@@ -722,4 +945,24 @@ mod tests {
     pub fn device_info_builder() {
         Builder::default().build();
     }
+
+    #[test]
+    pub fn memory_budget_device_local() {
+        let heap = |flags, budget, usage| MemoryHeapBudget {
+            budget,
+            flags,
+            size: budget * 2,
+            usage,
+        };
+        let budget = MemoryBudget {
+            heaps: Box::new([
+                heap(vk::MemoryHeapFlags::DEVICE_LOCAL, 256, 64),
+                heap(vk::MemoryHeapFlags::empty(), 1024, 512),
+                heap(vk::MemoryHeapFlags::DEVICE_LOCAL, 128, 32),
+            ]),
+        };
+
+        assert_eq!(budget.device_local_budget(), 384);
+        assert_eq!(budget.device_local_usage(), 96);
+    }
 }
@@ -419,6 +419,10 @@ impl GraphicPipeline {
             .map(|shader| shader.into())
             .collect::<Vec<Shader>>();
 
+        for shader in &shaders {
+            shader.check_device_support(&device)?;
+        }
+
         let vertex_input = shaders
             .iter()
             .find(|shader| shader.stage == vk::ShaderStageFlags::VERTEX)
@@ -73,6 +73,30 @@ impl From<vk::PhysicalDeviceAccelerationStructurePropertiesKHR<'_>>
     }
 }
 
+/// Features of the physical device for conditional rendering.
+///
+/// See
+/// [`VkPhysicalDeviceConditionalRenderingFeaturesEXT`](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkPhysicalDeviceConditionalRenderingFeaturesEXT.html)
+/// manual page.
+#[derive(Debug, Default)]
+pub struct ConditionalRenderingFeatures {
+    /// Indicates that conditional rendering is supported.
+    pub conditional_rendering: bool,
+
+    /// Indicates that a secondary command buffer can be executed while conditional rendering is
+    /// active in the primary command buffer.
+    pub inherited_conditional_rendering: bool,
+}
+
+impl From<vk::PhysicalDeviceConditionalRenderingFeaturesEXT<'_>> for ConditionalRenderingFeatures {
+    fn from(features: vk::PhysicalDeviceConditionalRenderingFeaturesEXT<'_>) -> Self {
+        Self {
+            conditional_rendering: features.conditional_rendering == vk::TRUE,
+            inherited_conditional_rendering: features.inherited_conditional_rendering == vk::TRUE,
+        }
+    }
+}
+
 /// Structure describing depth/stencil resolve properties that can be supported by an
 /// implementation.
 ///
@@ -144,6 +168,9 @@ pub struct PhysicalDevice {
     /// available.
     pub accel_struct_properties: Option<AccelerationStructureProperties>,
 
+    /// Describes the features of the device which relate to conditional rendering, if available.
+    pub conditional_rendering_features: ConditionalRenderingFeatures,
+
     /// Describes the properties of the device which relate to depth/stencil resolve operations.
     pub depth_stencil_resolve_properties: DepthStencilResolveProperties,
 
@@ -191,6 +218,10 @@ pub struct PhysicalDevice {
 
     /// Describes the properties of the device which relate to min/max sampler filtering.
     pub sampler_filter_minmax_properties: SamplerFilterMinmaxProperties,
+
+    /// Indicates that the device supports `VK_EXT_memory_budget`, which is required by
+    /// [`Device::memory_budget`](super::device::Device::memory_budget).
+    pub supports_memory_budget: bool,
 }
 
 impl PhysicalDevice {
@@ -230,6 +261,8 @@ impl PhysicalDevice {
         let mut features_v1_2 = vk::PhysicalDeviceVulkan12Features::default();
         let mut acceleration_structure_features =
             vk::PhysicalDeviceAccelerationStructureFeaturesKHR::default();
+        let mut conditional_rendering_features =
+            vk::PhysicalDeviceConditionalRenderingFeaturesEXT::default();
         let mut index_type_u8_features = vk::PhysicalDeviceIndexTypeUint8FeaturesEXT::default();
         let mut ray_query_features = vk::PhysicalDeviceRayQueryFeaturesKHR::default();
         let mut ray_trace_features = vk::PhysicalDeviceRayTracingPipelineFeaturesKHR::default();
@@ -237,6 +270,7 @@ impl PhysicalDevice {
             .push_next(&mut features_v1_1)
             .push_next(&mut features_v1_2)
             .push_next(&mut acceleration_structure_features)
+            .push_next(&mut conditional_rendering_features)
             .push_next(&mut index_type_u8_features)
             .push_next(&mut ray_query_features)
             .push_next(&mut ray_trace_features);
@@ -306,11 +340,16 @@ impl PhysicalDevice {
             .collect::<HashSet<_>>();
         let supports_accel_struct = extensions.contains(khr::acceleration_structure::NAME)
             && extensions.contains(khr::deferred_host_operations::NAME);
+        let supports_conditional_rendering = extensions.contains(ext::conditional_rendering::NAME);
         let supports_index_type_uint8 = extensions.contains(ext::index_type_uint8::NAME);
+        let supports_memory_budget = extensions.contains(ext::memory_budget::NAME);
         let supports_ray_query = extensions.contains(khr::ray_query::NAME);
         let supports_ray_trace = extensions.contains(khr::ray_tracing_pipeline::NAME);
 
         // Gather optional features and properties of the physical device
+        let conditional_rendering_features = supports_conditional_rendering
+            .then(|| conditional_rendering_features.into())
+            .unwrap_or_default();
         let index_type_uint8_features = supports_index_type_uint8
             .then(|| index_type_u8_features.into())
             .unwrap_or_default();
@@ -325,6 +364,7 @@ impl PhysicalDevice {
 
         Ok(Self {
             accel_struct_properties,
+            conditional_rendering_features,
             depth_stencil_resolve_properties,
             features_v1_0,
             features_v1_1,
@@ -341,6 +381,7 @@ impl PhysicalDevice {
             ray_trace_features,
             ray_trace_properties,
             sampler_filter_minmax_properties,
+            supports_memory_budget,
         })
     }
 }
@@ -122,6 +122,11 @@ impl RayTracePipeline {
             .into_iter()
             .map(|shader| shader.into())
             .collect::<Vec<Shader>>();
+
+        for shader in &shaders {
+            shader.check_device_support(device)?;
+        }
+
         let push_constants = shaders
             .iter()
             .map(|shader| shader.push_constant_range())
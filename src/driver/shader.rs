@@ -1,14 +1,19 @@
 //! Shader resource types
 
 use {
-    super::{DescriptorSetLayout, DriverError, VertexInputState, device::Device},
+    super::{
+        DescriptorSetLayout, DriverError, VertexInputState, device::Device,
+        physical_device::PhysicalDevice,
+    },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
     log::{debug, error, trace, warn},
     ordered_float::OrderedFloat,
     spirq::{
         ReflectConfig,
+        constant::ConstantValue,
         entry_point::EntryPoint,
+        spirv::{Capability, Dim, ExecutionMode, Op, StorageClass},
         ty::{DescriptorType, ScalarType, Type, VectorType},
         var::Variable,
     },
@@ -38,6 +43,65 @@ pub(crate) fn align_spriv(code: &[u8]) -> Result<&[u32], DriverError> {
 }
 
 #[profiling::function]
+fn capability_supported(physical_device: &PhysicalDevice, capability: Capability) -> bool {
+    let features_v1_0 = &physical_device.features_v1_0;
+    let features_v1_1 = &physical_device.features_v1_1;
+    let features_v1_2 = &physical_device.features_v1_2;
+
+    // Capabilities which are not listed are either always supported by Vulkan 1.2 devices or
+    // belong to extensions which are not checked here
+    match capability {
+        Capability::ClipDistance => features_v1_0.shader_clip_distance,
+        Capability::CullDistance => features_v1_0.shader_cull_distance,
+        Capability::DrawParameters => features_v1_1.shader_draw_parameters,
+        Capability::Float16 => features_v1_2.shader_float16,
+        Capability::Float64 => features_v1_0.shader_float64,
+        Capability::Geometry => features_v1_0.geometry_shader,
+        Capability::ImageGatherExtended => features_v1_0.shader_image_gather_extended,
+        Capability::Int8 => features_v1_2.shader_int8,
+        Capability::Int16 => features_v1_0.shader_int16,
+        Capability::Int64 => features_v1_0.shader_int64,
+        Capability::Int64Atomics => {
+            features_v1_2.shader_buffer_int64_atomics || features_v1_2.shader_shared_int64_atomics
+        }
+        Capability::MinLod => features_v1_0.shader_resource_min_lod,
+        Capability::MultiView => features_v1_1.multiview,
+        Capability::MultiViewport => features_v1_0.multi_viewport,
+        Capability::PhysicalStorageBufferAddresses => features_v1_2.buffer_device_address,
+        Capability::RayQueryKHR => physical_device.ray_query_features.ray_query,
+        Capability::RayTracingKHR => physical_device.ray_trace_features.ray_tracing_pipeline,
+        Capability::RuntimeDescriptorArray => features_v1_2.runtime_descriptor_array,
+        Capability::SampleRateShading => features_v1_0.sample_rate_shading,
+        Capability::StorageBuffer8BitAccess => features_v1_2.storage_buffer8_bit_access,
+        Capability::StorageBuffer16BitAccess => features_v1_1.storage_buffer16_bit_access,
+        Capability::StorageImageExtendedFormats => {
+            features_v1_0.shader_storage_image_extended_formats
+        }
+        Capability::StorageImageMultisample => features_v1_0.shader_storage_image_multisample,
+        Capability::StorageImageReadWithoutFormat => {
+            features_v1_0.shader_storage_image_read_without_format
+        }
+        Capability::StorageImageWriteWithoutFormat => {
+            features_v1_0.shader_storage_image_write_without_format
+        }
+        Capability::StorageInputOutput16 => features_v1_1.storage_input_output16,
+        Capability::StoragePushConstant8 => features_v1_2.storage_push_constant8,
+        Capability::StoragePushConstant16 => features_v1_1.storage_push_constant16,
+        Capability::Tessellation => features_v1_0.tessellation_shader,
+        Capability::UniformAndStorageBuffer8BitAccess => {
+            features_v1_2.uniform_and_storage_buffer8_bit_access
+        }
+        Capability::UniformAndStorageBuffer16BitAccess => {
+            features_v1_1.uniform_and_storage_buffer16_bit_access
+        }
+        Capability::VariablePointers => features_v1_1.variable_pointers,
+        Capability::VariablePointersStorageBuffer => features_v1_1.variable_pointers_storage_buffer,
+        Capability::VulkanMemoryModel => features_v1_2.vulkan_memory_model,
+        Capability::VulkanMemoryModelDeviceScope => features_v1_2.vulkan_memory_model_device_scope,
+        _ => true,
+    }
+}
+
 fn guess_immutable_sampler(binding_name: &str) -> SamplerInfo {
     const INVALID_ERR: &str = "Invalid sampler specification";
 
@@ -91,6 +155,78 @@ fn guess_immutable_sampler(binding_name: &str) -> SamplerInfo {
     info.build()
 }
 
+/// Returns the view type an image bound to a descriptor of the given reflected type must use, if
+/// the shader declared an image with a dimensionality Vulkan views can represent.
+fn image_view_type(ty: &Type) -> Option<vk::ImageViewType> {
+    let (dim, is_array) = match ty {
+        Type::CombinedImageSampler(ty) => (ty.sampled_img_ty.dim, ty.sampled_img_ty.is_array),
+        Type::SampledImage(ty) => (ty.dim, ty.is_array),
+        Type::StorageImage(ty) => (ty.dim, ty.is_array),
+        _ => return None,
+    };
+
+    Some(match (dim, is_array) {
+        (Dim::Dim1D, false) => vk::ImageViewType::TYPE_1D,
+        (Dim::Dim1D, true) => vk::ImageViewType::TYPE_1D_ARRAY,
+        (Dim::Dim2D, false) => vk::ImageViewType::TYPE_2D,
+        (Dim::Dim2D, true) => vk::ImageViewType::TYPE_2D_ARRAY,
+        (Dim::Dim3D, _) => vk::ImageViewType::TYPE_3D,
+        (Dim::DimCube, false) => vk::ImageViewType::CUBE,
+        (Dim::DimCube, true) => vk::ImageViewType::CUBE_ARRAY,
+        _ => return None,
+    })
+}
+
+/// Returns `true` if descriptor sets zero through `descriptor_set_idx` are identically defined in
+/// both sets of bindings, as required for pipeline layouts to be compatible for that set.
+pub(super) fn descriptor_sets_compatible(
+    lhs: &DescriptorBindingMap,
+    rhs: &DescriptorBindingMap,
+    descriptor_set_idx: u32,
+) -> bool {
+    let binding_count = |bindings: &DescriptorBindingMap| {
+        bindings
+            .keys()
+            .filter(|descriptor| descriptor.set <= descriptor_set_idx)
+            .count()
+    };
+
+    binding_count(lhs) == binding_count(rhs)
+        && lhs
+            .iter()
+            .filter(|(descriptor, _)| descriptor.set <= descriptor_set_idx)
+            .all(|(descriptor, (lhs_info, lhs_stage_flags))| {
+                rhs.get(descriptor)
+                    .is_some_and(|(rhs_info, rhs_stage_flags)| {
+                        lhs_info.descriptor_type() == rhs_info.descriptor_type()
+                            && lhs_info.binding_count() == rhs_info.binding_count()
+                            && lhs_info.sampler_info() == rhs_info.sampler_info()
+                            && lhs_stage_flags == rhs_stage_flags
+                    })
+            })
+}
+
+/// Returns the layout of a storage buffer block of the given reflected type, if the block ends in
+/// a runtime-sized array.
+fn storage_buffer_layout(ty: &Type) -> Option<StorageBufferLayout> {
+    let Type::Struct(ty) = ty else {
+        return None;
+    };
+    let member = ty.members.last()?;
+    let Type::Array(array_ty) = &member.ty else {
+        return None;
+    };
+
+    if array_ty.element_count.is_some() {
+        return None;
+    }
+
+    Some(StorageBufferLayout {
+        offset: member.offset? as _,
+        stride: array_ty.stride.filter(|&stride| stride > 0)? as _,
+    })
+}
+
 /// Tuple of descriptor set index and binding index.
 ///
 /// This is a generic representation of the descriptor binding point within the shader and not a
@@ -119,12 +255,13 @@ impl From<(u32, u32)> for Descriptor {
 #[derive(Clone, Copy, Debug)]
 pub(crate) enum DescriptorInfo {
     AccelerationStructure(u32),
-    CombinedImageSampler(u32, SamplerInfo, bool), //count, sampler, is-manually-defined?
-    InputAttachment(u32, u32),                    //count, input index,
-    SampledImage(u32),
-    Sampler(u32, SamplerInfo, bool), //count, sampler, is-manually-defined?
-    StorageBuffer(u32),
-    StorageImage(u32),
+    //count, sampler, is-manually-defined?, view type
+    CombinedImageSampler(u32, SamplerInfo, bool, Option<vk::ImageViewType>),
+    InputAttachment(u32, u32),                       //count, input index,
+    SampledImage(u32, Option<vk::ImageViewType>),    //count, view type
+    Sampler(u32, SamplerInfo, bool),                 //count, sampler, is-manually-defined?
+    StorageBuffer(u32, Option<StorageBufferLayout>), //count, runtime array layout
+    StorageImage(u32, Option<vk::ImageViewType>),    //count, view type
     StorageTexelBuffer(u32),
     UniformBuffer(u32),
     UniformTexelBuffer(u32),
@@ -136,10 +273,10 @@ impl DescriptorInfo {
             Self::AccelerationStructure(binding_count) => binding_count,
             Self::CombinedImageSampler(binding_count, ..) => binding_count,
             Self::InputAttachment(binding_count, _) => binding_count,
-            Self::SampledImage(binding_count) => binding_count,
+            Self::SampledImage(binding_count, _) => binding_count,
             Self::Sampler(binding_count, ..) => binding_count,
-            Self::StorageBuffer(binding_count) => binding_count,
-            Self::StorageImage(binding_count) => binding_count,
+            Self::StorageBuffer(binding_count, _) => binding_count,
+            Self::StorageImage(binding_count, _) => binding_count,
             Self::StorageTexelBuffer(binding_count) => binding_count,
             Self::UniformBuffer(binding_count) => binding_count,
             Self::UniformTexelBuffer(binding_count) => binding_count,
@@ -151,19 +288,39 @@ impl DescriptorInfo {
             Self::AccelerationStructure(_) => vk::DescriptorType::ACCELERATION_STRUCTURE_KHR,
             Self::CombinedImageSampler(..) => vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
             Self::InputAttachment(..) => vk::DescriptorType::INPUT_ATTACHMENT,
-            Self::SampledImage(_) => vk::DescriptorType::SAMPLED_IMAGE,
+            Self::SampledImage(..) => vk::DescriptorType::SAMPLED_IMAGE,
             Self::Sampler(..) => vk::DescriptorType::SAMPLER,
-            Self::StorageBuffer(_) => vk::DescriptorType::STORAGE_BUFFER,
-            Self::StorageImage(_) => vk::DescriptorType::STORAGE_IMAGE,
+            Self::StorageBuffer(..) => vk::DescriptorType::STORAGE_BUFFER,
+            Self::StorageImage(..) => vk::DescriptorType::STORAGE_IMAGE,
             Self::StorageTexelBuffer(_) => vk::DescriptorType::STORAGE_TEXEL_BUFFER,
             Self::UniformBuffer(_) => vk::DescriptorType::UNIFORM_BUFFER,
             Self::UniformTexelBuffer(_) => vk::DescriptorType::UNIFORM_TEXEL_BUFFER,
         }
     }
 
+    /// Returns the image view type the shader declared for this descriptor, if it is an image and
+    /// the dimensionality maps to a Vulkan view type.
+    pub fn image_view_type(self) -> Option<vk::ImageViewType> {
+        match self {
+            Self::CombinedImageSampler(.., view_ty)
+            | Self::SampledImage(_, view_ty)
+            | Self::StorageImage(_, view_ty) => view_ty,
+            _ => None,
+        }
+    }
+
+    /// Returns the layout of the runtime-sized array which ends the storage buffer block the shader
+    /// declared for this descriptor, if any.
+    pub fn storage_buffer_layout(self) -> Option<StorageBufferLayout> {
+        match self {
+            Self::StorageBuffer(_, layout) => layout,
+            _ => None,
+        }
+    }
+
     fn sampler_info(self) -> Option<SamplerInfo> {
         match self {
-            Self::CombinedImageSampler(_, sampler_info, _) | Self::Sampler(_, sampler_info, _) => {
+            Self::CombinedImageSampler(_, sampler_info, ..) | Self::Sampler(_, sampler_info, _) => {
                 Some(sampler_info)
             }
             _ => None,
@@ -175,10 +332,10 @@ impl DescriptorInfo {
             Self::AccelerationStructure(binding_count) => binding_count,
             Self::CombinedImageSampler(binding_count, ..) => binding_count,
             Self::InputAttachment(binding_count, _) => binding_count,
-            Self::SampledImage(binding_count) => binding_count,
+            Self::SampledImage(binding_count, _) => binding_count,
             Self::Sampler(binding_count, ..) => binding_count,
-            Self::StorageBuffer(binding_count) => binding_count,
-            Self::StorageImage(binding_count) => binding_count,
+            Self::StorageBuffer(binding_count, _) => binding_count,
+            Self::StorageImage(binding_count, _) => binding_count,
             Self::StorageTexelBuffer(binding_count) => binding_count,
             Self::UniformBuffer(binding_count) => binding_count,
             Self::UniformTexelBuffer(binding_count) => binding_count,
@@ -186,6 +343,17 @@ impl DescriptorInfo {
     }
 }
 
+/// The reflected layout of a storage buffer block which ends in a runtime-sized array, such as
+/// `buffer Vertices { uint count; Vertex vertices[]; }`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct StorageBufferLayout {
+    /// The byte offset of the first array element within the block.
+    pub offset: vk::DeviceSize,
+
+    /// The byte stride between array elements.
+    pub stride: vk::DeviceSize,
+}
+
 #[derive(Debug)]
 pub(crate) struct PipelineDescriptorInfo {
     pub layouts: BTreeMap<u32, DescriptorSetLayout>,
@@ -212,37 +380,14 @@ impl PipelineDescriptorInfo {
 
         //trace!("descriptor_bindings: {:#?}", &descriptor_bindings);
 
-        let mut sampler_info_binding_count = HashMap::<_, u32>::with_capacity(
-            descriptor_bindings
-                .values()
-                .filter(|(descriptor_info, _)| descriptor_info.sampler_info().is_some())
-                .count(),
-        );
+        let sampler_info_binding_count = Self::sampler_info_binding_count(descriptor_bindings);
 
-        for (sampler_info, binding_count) in
-            descriptor_bindings
-                .values()
-                .filter_map(|(descriptor_info, _)| {
-                    descriptor_info
-                        .sampler_info()
-                        .map(|sampler_info| (sampler_info, descriptor_info.binding_count()))
-                })
-        {
-            sampler_info_binding_count
-                .entry(sampler_info)
-                .and_modify(|sampler_info_binding_count| {
-                    *sampler_info_binding_count = binding_count.max(*sampler_info_binding_count);
-                })
-                .or_insert(binding_count);
+        // Collecting through a Result loses the size hint, so reserve the exact count up front
+        let mut samplers = HashMap::with_capacity(sampler_info_binding_count.len());
+        for &sampler_info in sampler_info_binding_count.keys() {
+            samplers.insert(sampler_info, Sampler::create(device, sampler_info)?);
         }
 
-        let mut samplers = sampler_info_binding_count
-            .keys()
-            .copied()
-            .map(|sampler_info| {
-                Sampler::create(device, sampler_info).map(|sampler| (sampler_info, sampler))
-            })
-            .collect::<Result<HashMap<_, _>, _>>()?;
         let immutable_samplers = sampler_info_binding_count
             .iter()
             .map(|(sampler_info, &binding_count)| {
@@ -334,6 +479,38 @@ impl PipelineDescriptorInfo {
             samplers,
         })
     }
+
+    /// Returns the largest binding count of each distinct immutable sampler, using a map which is
+    /// sized up front so that it does not reallocate while being filled.
+    fn sampler_info_binding_count(
+        descriptor_bindings: &DescriptorBindingMap,
+    ) -> HashMap<SamplerInfo, u32> {
+        let mut sampler_info_binding_count = HashMap::<_, u32>::with_capacity(
+            descriptor_bindings
+                .values()
+                .filter(|(descriptor_info, _)| descriptor_info.sampler_info().is_some())
+                .count(),
+        );
+
+        for (sampler_info, binding_count) in
+            descriptor_bindings
+                .values()
+                .filter_map(|(descriptor_info, _)| {
+                    descriptor_info
+                        .sampler_info()
+                        .map(|sampler_info| (sampler_info, descriptor_info.binding_count()))
+                })
+        {
+            sampler_info_binding_count
+                .entry(sampler_info)
+                .and_modify(|sampler_info_binding_count| {
+                    *sampler_info_binding_count = binding_count.max(*sampler_info_binding_count);
+                })
+                .or_insert(binding_count);
+        }
+
+        sampler_info_binding_count
+    }
 }
 
 pub(crate) struct Sampler {
@@ -913,14 +1090,14 @@ impl Shader {
     pub(super) fn descriptor_bindings(&self) -> DescriptorBindingMap {
         let mut res = DescriptorBindingMap::default();
 
-        for (name, descriptor, desc_ty, binding_count) in
+        for (name, descriptor, desc_ty, ty, binding_count) in
             self.entry_point.vars.iter().filter_map(|var| match var {
                 Variable::Descriptor {
                     name,
                     desc_bind,
                     desc_ty,
+                    ty,
                     nbind,
-                    ..
                 } => Some((
                     name,
                     Descriptor {
@@ -928,6 +1105,7 @@ impl Shader {
                         binding: desc_bind.bind(),
                     },
                     desc_ty,
+                    ty,
                     *nbind,
                 )),
                 _ => None,
@@ -954,12 +1132,15 @@ impl Shader {
                         binding_count,
                         sampler_info,
                         is_manually_defined,
+                        image_view_type(ty),
                     )
                 }
                 DescriptorType::InputAttachment(attachment) => {
                     DescriptorInfo::InputAttachment(binding_count, *attachment)
                 }
-                DescriptorType::SampledImage() => DescriptorInfo::SampledImage(binding_count),
+                DescriptorType::SampledImage() => {
+                    DescriptorInfo::SampledImage(binding_count, image_view_type(ty))
+                }
                 DescriptorType::Sampler() => {
                     let (sampler_info, is_manually_defined) =
                         self.image_sampler(descriptor, name.as_deref().unwrap_or_default());
@@ -967,10 +1148,10 @@ impl Shader {
                     DescriptorInfo::Sampler(binding_count, sampler_info, is_manually_defined)
                 }
                 DescriptorType::StorageBuffer(_access_ty) => {
-                    DescriptorInfo::StorageBuffer(binding_count)
+                    DescriptorInfo::StorageBuffer(binding_count, storage_buffer_layout(ty))
                 }
                 DescriptorType::StorageImage(_access_ty) => {
-                    DescriptorInfo::StorageImage(binding_count)
+                    DescriptorInfo::StorageImage(binding_count, image_view_type(ty))
                 }
                 DescriptorType::StorageTexelBuffer(_access_ty) => {
                     DescriptorInfo::StorageTexelBuffer(binding_count)
@@ -994,6 +1175,23 @@ impl Shader {
             .unwrap_or_else(|| (guess_immutable_sampler(name), false))
     }
 
+    /// Returns the manually-defined image samplers which do not match any sampler or combined
+    /// image sampler in `descriptor_bindings`.
+    pub(super) fn unmatched_image_samplers<'a>(
+        &'a self,
+        descriptor_bindings: &'a DescriptorBindingMap,
+    ) -> impl Iterator<Item = Descriptor> + 'a {
+        self.image_samplers.keys().copied().filter(|descriptor| {
+            !matches!(
+                descriptor_bindings.get(descriptor),
+                Some((
+                    DescriptorInfo::CombinedImageSampler(..) | DescriptorInfo::Sampler(..),
+                    _
+                ))
+            )
+        })
+    }
+
     #[profiling::function]
     pub(super) fn merge_descriptor_bindings(
         descriptor_bindings: impl IntoIterator<Item = DescriptorBindingMap>,
@@ -1007,13 +1205,23 @@ impl Shader {
                         return false;
                     }
                 }
-                DescriptorInfo::CombinedImageSampler(lhs, lhs_sampler, lhs_is_manually_defined) => {
+                DescriptorInfo::CombinedImageSampler(
+                    lhs,
+                    lhs_sampler,
+                    lhs_is_manually_defined,
+                    lhs_view_ty,
+                ) => {
                     if let DescriptorInfo::CombinedImageSampler(
                         rhs,
                         rhs_sampler,
                         rhs_is_manually_defined,
+                        rhs_view_ty,
                     ) = rhs
                     {
+                        if *lhs_view_ty != rhs_view_ty {
+                            return false;
+                        }
+
                         // Allow one of the samplers to be manually defined (only one!)
                         if *lhs_is_manually_defined && rhs_is_manually_defined {
                             return false;
@@ -1037,8 +1245,12 @@ impl Shader {
                         return false;
                     }
                 }
-                DescriptorInfo::SampledImage(lhs) => {
-                    if let DescriptorInfo::SampledImage(rhs) = rhs {
+                DescriptorInfo::SampledImage(lhs, lhs_view_ty) => {
+                    if let DescriptorInfo::SampledImage(rhs, rhs_view_ty) = rhs {
+                        if *lhs_view_ty != rhs_view_ty {
+                            return false;
+                        }
+
                         (lhs, rhs)
                     } else {
                         return false;
@@ -1059,15 +1271,19 @@ impl Shader {
                         return false;
                     }
                 }
-                DescriptorInfo::StorageBuffer(lhs) => {
-                    if let DescriptorInfo::StorageBuffer(rhs) = rhs {
+                DescriptorInfo::StorageBuffer(lhs, _) => {
+                    if let DescriptorInfo::StorageBuffer(rhs, _) = rhs {
                         (lhs, rhs)
                     } else {
                         return false;
                     }
                 }
-                DescriptorInfo::StorageImage(lhs) => {
-                    if let DescriptorInfo::StorageImage(rhs) = rhs {
+                DescriptorInfo::StorageImage(lhs, lhs_view_ty) => {
+                    if let DescriptorInfo::StorageImage(rhs, rhs_view_ty) = rhs {
+                        if *lhs_view_ty != rhs_view_ty {
+                            return false;
+                        }
+
                         (lhs, rhs)
                     } else {
                         return false;
@@ -1155,6 +1371,180 @@ impl Shader {
             })
     }
 
+    /// Returns the capabilities declared by the `OpCapability` instructions of this shader.
+    pub(super) fn capabilities(&self) -> Vec<Capability> {
+        let Some(mut words) = align_spriv(&self.spirv)
+            .ok()
+            .and_then(|words| words.get(5..))
+        else {
+            return vec![];
+        };
+        let mut capabilities = vec![];
+
+        while let Some(&first_word) = words.first() {
+            let word_count = (first_word >> 16) as usize;
+            if word_count == 0 || word_count > words.len() {
+                break;
+            }
+
+            let (instruction, rest) = words.split_at(word_count);
+            words = rest;
+
+            match (Op::from_u32(first_word & 0xffff), &instruction[1..]) {
+                (Some(Op::Capability), &[capability]) => {
+                    capabilities.extend(Capability::from_u32(capability));
+                }
+                // Capabilities must be declared before all other instructions but extensions
+                (Some(Op::Capability | Op::Extension), _) => (),
+                _ => break,
+            }
+        }
+
+        capabilities
+    }
+
+    /// Returns an error if the device cannot run this shader because it uses a newer SPIR-V
+    /// version or a capability which the device does not support.
+    ///
+    /// This catches shaders which would otherwise fail during pipeline creation with an error
+    /// which does not identify the cause, or not fail at all and misbehave.
+    pub(super) fn check_device_support(&self, device: &Device) -> Result<(), DriverError> {
+        // Devices are used at Vulkan 1.2 at most, which consumes SPIR-V 1.5 at most
+        let api_version = device
+            .physical_device
+            .properties_v1_0
+            .api_version
+            .min(vk::API_VERSION_1_2);
+        let max_spirv_version = match (
+            vk::api_version_major(api_version),
+            vk::api_version_minor(api_version),
+        ) {
+            (1, 0) => (1, 0),
+            (1, 1) => (1, 3),
+            _ => (1, 5),
+        };
+
+        if let Some((major, minor)) = self
+            .spirv_version()
+            .filter(|&spirv_version| spirv_version > max_spirv_version)
+        {
+            warn!(
+                "shader uses SPIR-V {major}.{minor} but the device supports {}.{}",
+                max_spirv_version.0, max_spirv_version.1
+            );
+
+            return Err(DriverError::Unsupported);
+        }
+
+        let unsupported_capabilities = self
+            .capabilities()
+            .into_iter()
+            .filter(|&capability| !capability_supported(&device.physical_device, capability))
+            .collect::<Vec<_>>();
+
+        if !unsupported_capabilities.is_empty() {
+            warn!("shader uses unsupported SPIR-V capabilities: {unsupported_capabilities:?}");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the workgroup size of a compute shader, if it was declared using literal values
+    /// instead of specialization constants.
+    pub(super) fn local_size(&self) -> Option<[u32; 3]> {
+        let exec_mode = self
+            .entry_point
+            .exec_modes
+            .iter()
+            .find(|exec_mode| exec_mode.exec_mode == ExecutionMode::LocalSize)?;
+        let mut local_size = [1; 3];
+
+        for (size, operand) in local_size.iter_mut().zip(&exec_mode.operands) {
+            *size = match operand.value {
+                ConstantValue::U32(value) => value,
+                _ => return None,
+            };
+        }
+
+        Some(local_size)
+    }
+
+    /// Returns the number of bytes of workgroup shared memory declared by this shader, if the
+    /// types of all shared variables could be sized.
+    ///
+    /// Shared variables are measured as if they were tightly packed, so the result is a lower
+    /// bound of the memory used by the implementation.
+    pub(super) fn shared_memory_size(&self) -> Option<u32> {
+        let mut words = align_spriv(&self.spirv).ok()?.get(5..)?;
+        let mut constants = HashMap::new();
+        let mut pointers = HashMap::new();
+        let mut sizes = HashMap::new();
+        let mut shared_memory_size = 0;
+
+        while let Some(&first_word) = words.first() {
+            let word_count = (first_word >> 16) as usize;
+            if word_count == 0 || word_count > words.len() {
+                return None;
+            }
+
+            let (instruction, rest) = words.split_at(word_count);
+            words = rest;
+
+            match (Op::from_u32(first_word & 0xffff), &instruction[1..]) {
+                (Some(Op::TypeBool), &[id, ..]) => {
+                    sizes.insert(id, 4);
+                }
+                (Some(Op::TypeInt | Op::TypeFloat), &[id, width, ..]) => {
+                    sizes.insert(id, width / 8);
+                }
+                (Some(Op::TypeVector | Op::TypeMatrix), &[id, component_ty, count]) => {
+                    if let Some(&size) = sizes.get(&component_ty) {
+                        sizes.insert(id, size * count);
+                    }
+                }
+                (Some(Op::TypeArray), &[id, element_ty, length]) => {
+                    if let (Some(&size), Some(&length)) =
+                        (sizes.get(&element_ty), constants.get(&length))
+                    {
+                        sizes.insert(id, size * length);
+                    }
+                }
+                (Some(Op::TypeStruct), &[id, ref members @ ..]) => {
+                    if let Some(size) = members
+                        .iter()
+                        .map(|member| sizes.get(member).copied())
+                        .sum::<Option<u32>>()
+                    {
+                        sizes.insert(id, size);
+                    }
+                }
+                (Some(Op::TypePointer), &[id, _, ty]) => {
+                    pointers.insert(id, ty);
+                }
+                (Some(Op::Constant | Op::SpecConstant), &[_, id, value, ..]) => {
+                    constants.insert(id, value);
+                }
+                (Some(Op::Variable), &[pointer_ty, _, storage_class, ..])
+                    if storage_class == StorageClass::Workgroup as u32 =>
+                {
+                    shared_memory_size += sizes.get(pointers.get(&pointer_ty)?)?;
+                }
+                _ => (),
+            }
+        }
+
+        Some(shared_memory_size)
+    }
+
+    /// Returns the `(major, minor)` SPIR-V version declared in the module header.
+    pub(super) fn spirv_version(&self) -> Option<(u32, u32)> {
+        let version = *align_spriv(&self.spirv).ok()?.get(1)?;
+
+        Some(((version >> 16) & 0xff, (version >> 8) & 0xff))
+    }
+
     #[profiling::function]
     fn reflect_entry_point(
         entry_name: &str,
@@ -1594,4 +1984,371 @@ mod tests {
 
         assert_eq!(info, builder);
     }
+
+    #[test]
+    pub fn uniform_buffer_descriptor_bindings() {
+        let shader = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+
+                layout(set = 0, binding = 0) uniform Params {
+                    vec4 scale;
+                } params;
+
+                layout(set = 0, binding = 1) restrict writeonly buffer Output {
+                    vec4 values[];
+                } output_buf;
+
+                void main()
+                {
+                    output_buf.values[gl_GlobalInvocationID.x] = params.scale;
+                }
+                "#,
+                comp
+            )
+            .as_slice(),
+        )
+        .build();
+        let descriptor_bindings = shader.descriptor_bindings();
+        let (params, _) = descriptor_bindings[&Descriptor::from(0)];
+        let (output_buf, _) = descriptor_bindings[&Descriptor::from(1)];
+
+        assert_eq!(params.descriptor_type(), vk::DescriptorType::UNIFORM_BUFFER);
+        assert_eq!(params.binding_count(), 1);
+        assert_eq!(
+            output_buf.descriptor_type(),
+            vk::DescriptorType::STORAGE_BUFFER
+        );
+    }
+
+    #[test]
+    pub fn descriptor_sets_compatible() {
+        let blur = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+
+                layout(set = 0, binding = 0, rgba8) restrict readonly uniform image2D src_image;
+                layout(set = 0, binding = 1, rgba8) restrict writeonly uniform image2D dst_image;
+                layout(set = 1, binding = 0) uniform Params {
+                    float radius;
+                } params;
+
+                void main()
+                {
+                    ivec2 coord = ivec2(gl_GlobalInvocationID.xy);
+                    imageStore(dst_image, coord, imageLoad(src_image, coord) * params.radius);
+                }
+                "#,
+                comp
+            )
+            .as_slice(),
+        )
+        .build();
+        let sharpen = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+
+                layout(set = 0, binding = 0, rgba8) restrict readonly uniform image2D src_image;
+                layout(set = 0, binding = 1, rgba8) restrict writeonly uniform image2D dst_image;
+                layout(set = 1, binding = 0) restrict readonly buffer Params {
+                    float amount;
+                } params;
+
+                void main()
+                {
+                    ivec2 coord = ivec2(gl_GlobalInvocationID.xy);
+                    imageStore(dst_image, coord, imageLoad(src_image, coord) * params.amount);
+                }
+                "#,
+                comp
+            )
+            .as_slice(),
+        )
+        .build();
+        let blur = blur.descriptor_bindings();
+        let sharpen = sharpen.descriptor_bindings();
+
+        assert!(super::descriptor_sets_compatible(&blur, &sharpen, 0));
+        assert!(!super::descriptor_sets_compatible(&blur, &sharpen, 1));
+        assert!(super::descriptor_sets_compatible(&blur, &blur, 1));
+    }
+
+    #[test]
+    pub fn capabilities() {
+        let shader = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+                #extension GL_ARB_gpu_shader_int64 : require
+
+                layout(set = 0, binding = 0) buffer Data {
+                    uint64_t value;
+                } data;
+
+                void main()
+                {
+                    data.value += 1ul;
+                }
+                "#,
+                comp
+            )
+            .as_slice(),
+        )
+        .build();
+
+        let capabilities = shader.capabilities();
+
+        assert!(capabilities.contains(&Capability::Shader));
+        assert!(capabilities.contains(&Capability::Int64));
+        assert!(!capabilities.contains(&Capability::Float64));
+    }
+
+    #[test]
+    pub fn local_size() {
+        let shader = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+
+                layout(local_size_x = 8, local_size_y = 4) in;
+
+                void main()
+                {
+                }
+                "#,
+                comp
+            )
+            .as_slice(),
+        )
+        .build();
+
+        assert_eq!(shader.local_size(), Some([8, 4, 1]));
+    }
+
+    #[test]
+    pub fn spirv_version() {
+        let shader = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+
+                void main()
+                {
+                }
+                "#,
+                comp,
+                vulkan1_2
+            )
+            .as_slice(),
+        )
+        .build();
+
+        // Vulkan 1.2 targets SPIR-V 1.5
+        assert_eq!(shader.spirv_version(), Some((1, 5)));
+    }
+
+    #[test]
+    pub fn storage_buffer_layout() {
+        let shader = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+
+                struct Vertex {
+                    vec3 position;
+                    vec2 uv;
+                };
+
+                layout(set = 0, binding = 0) restrict readonly buffer Vertices {
+                    uint count;
+                    Vertex vertices[];
+                } vertex_buf;
+
+                layout(set = 0, binding = 1) restrict writeonly buffer Output {
+                    vec4 value;
+                } output_buf;
+
+                void main()
+                {
+                    output_buf.value = vec4(vertex_buf.vertices[vertex_buf.count - 1].position, 1.0);
+                }
+                "#,
+                comp
+            )
+            .as_slice(),
+        )
+        .build();
+        let descriptor_bindings = shader.descriptor_bindings();
+        let (vertex_buf, _) = descriptor_bindings[&Descriptor::from(0)];
+        let (output_buf, _) = descriptor_bindings[&Descriptor::from(1)];
+
+        assert_eq!(
+            vertex_buf.storage_buffer_layout(),
+            Some(StorageBufferLayout {
+                offset: 16,
+                stride: 32,
+            })
+        );
+        assert_eq!(output_buf.storage_buffer_layout(), None);
+    }
+
+    #[test]
+    pub fn unmatched_image_samplers() {
+        let shader = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+
+                layout(set = 0, binding = 0) uniform sampler2D src_image;
+                layout(set = 0, binding = 1, rgba8) restrict writeonly uniform image2D dst_image;
+
+                void main()
+                {
+                    ivec2 coord = ivec2(gl_GlobalInvocationID.xy);
+                    imageStore(dst_image, coord, texelFetch(src_image, coord, 0));
+                }
+                "#,
+                comp
+            )
+            .as_slice(),
+        )
+        .image_sampler(0, Info::default())
+        .image_sampler(1, Info::default())
+        .image_sampler(2, Info::default())
+        .build();
+        let descriptor_bindings = shader.descriptor_bindings();
+        let mut unmatched = shader
+            .unmatched_image_samplers(&descriptor_bindings)
+            .collect::<Vec<_>>();
+        unmatched.sort();
+
+        assert_eq!(unmatched, [Descriptor::from(1), Descriptor::from(2)]);
+    }
+
+    #[test]
+    pub fn push_constant_range_std430() {
+        #[allow(dead_code)]
+        #[repr(C)]
+        struct PushConstants {
+            view_proj: [f32; 16],
+            light_dir: [f32; 3],
+            max_distance: f32,
+            step_count: u32,
+        }
+
+        let shader = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+
+                layout(push_constant) uniform PushConstants {
+                    mat4 view_proj;
+                    vec3 light_dir;
+                    float max_distance;
+                    uint step_count;
+                } push_const;
+
+                layout(set = 0, binding = 0) restrict writeonly buffer Output {
+                    vec4 value;
+                } output_buf;
+
+                void main()
+                {
+                    vec3 ray = push_const.light_dir * push_const.max_distance;
+                    output_buf.value = push_const.view_proj * vec4(ray, float(push_const.step_count));
+                }
+                "#,
+                comp
+            )
+            .as_slice(),
+        )
+        .build();
+        let push_const = shader.push_constant_range().unwrap();
+
+        assert_eq!(push_const.offset, 0);
+        assert_eq!(push_const.size as usize, size_of::<PushConstants>());
+        assert_eq!(std::mem::offset_of!(PushConstants, light_dir), 64);
+        assert_eq!(std::mem::offset_of!(PushConstants, max_distance), 76);
+        assert_eq!(std::mem::offset_of!(PushConstants, step_count), 80);
+    }
+
+    #[test]
+    pub fn sampler_info_binding_count() {
+        let shader = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+
+                layout(set = 0, binding = 0) uniform sampler2D linear_image;
+                layout(set = 0, binding = 1) uniform sampler2D nearest_image;
+                layout(set = 0, binding = 2) uniform sampler2D linear_images[4];
+                layout(set = 0, binding = 3, rgba8) restrict writeonly uniform image2D dst_image;
+
+                void main()
+                {
+                    ivec2 coord = ivec2(gl_GlobalInvocationID.xy);
+                    vec4 color = texelFetch(linear_image, coord, 0)
+                        + texelFetch(nearest_image, coord, 0)
+                        + texelFetch(linear_images[3], coord, 0);
+                    imageStore(dst_image, coord, color);
+                }
+                "#,
+                comp
+            )
+            .as_slice(),
+        )
+        .image_sampler(0, Info::LINEAR)
+        .image_sampler(1, Info::NEAREST)
+        .image_sampler(2, Info::LINEAR)
+        .build();
+        let descriptor_bindings = shader.descriptor_bindings();
+        let sampler_info_binding_count =
+            PipelineDescriptorInfo::sampler_info_binding_count(&descriptor_bindings);
+
+        assert_eq!(sampler_info_binding_count.len(), 2);
+        assert_eq!(sampler_info_binding_count[&Info::LINEAR.build()], 4);
+        assert_eq!(sampler_info_binding_count[&Info::NEAREST.build()], 1);
+
+        // One entry is reserved per sampler binding, so filling the map never grows it
+        assert_eq!(
+            sampler_info_binding_count.capacity(),
+            HashMap::<Info, u32>::with_capacity(3).capacity()
+        );
+    }
+
+    #[test]
+    pub fn shared_memory_size() {
+        let shader = Shader::new_compute(
+            inline_spirv::inline_spirv!(
+                r#"
+                #version 460 core
+
+                layout(local_size_x = 64) in;
+
+                layout(set = 0, binding = 0) restrict writeonly buffer Output {
+                    vec4 values[];
+                } output_buf;
+
+                shared float partial[64];
+                shared vec4 tile[16];
+
+                void main()
+                {
+                    uint idx = gl_LocalInvocationID.x;
+                    partial[idx] = float(idx);
+                    tile[idx % 16] = vec4(partial[63 - idx]);
+                    output_buf.values[idx] = tile[idx / 4];
+                }
+                "#,
+                comp
+            )
+            .as_slice(),
+        )
+        .build();
+
+        assert_eq!(shader.shared_memory_size(), Some(512));
+    }
 }
@@ -1,6 +1,65 @@
 //! Rendering operations and command submission.
 //!
+//! # Chaining compute passes
 //!
+//! Passes declare how they access each node (`read_descriptor`, `write_descriptor`, and so on)
+//! and the graph uses those declarations to insert the required pipeline barriers when it is
+//! resolved. A sequence of compute passes over the same image therefore needs no manual
+//! synchronization: each pass which reads the image waits on the shader writes of the pass before
+//! it.
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use ash::vk;
+//! # use screen_13::driver::DriverError;
+//! # use screen_13::driver::device::{Device, DeviceInfo};
+//! # use screen_13::driver::image::{Image, ImageInfo};
+//! # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+//! # use screen_13::driver::shader::Shader;
+//! # use screen_13::graph::RenderGraph;
+//! # fn main() -> Result<(), DriverError> {
+//! # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+//! # let info = ImageInfo::image_2d(64, 64, vk::Format::R8G8B8A8_UNORM, vk::ImageUsageFlags::STORAGE);
+//! # let image = Image::create(&device, info)?;
+//! # let scratch = Image::create(&device, info)?;
+//! # let shader = Shader::new_compute([0u8; 1].as_slice());
+//! # let decode = Arc::new(ComputePipeline::create(&device, ComputePipelineInfo::default(), shader)?);
+//! # let shader = Shader::new_compute([0u8; 1].as_slice());
+//! # let blur = Arc::new(ComputePipeline::create(&device, ComputePipelineInfo::default(), shader)?);
+//! # let shader = Shader::new_compute([0u8; 1].as_slice());
+//! # let tonemap = Arc::new(ComputePipeline::create(&device, ComputePipelineInfo::default(), shader)?);
+//! let mut render_graph = RenderGraph::new();
+//! let image = render_graph.bind_node(image);
+//! let scratch = render_graph.bind_node(scratch);
+//!
+//! render_graph
+//!     .begin_pass("decode")
+//!     .bind_pipeline(&decode)
+//!     .write_descriptor(0, image)
+//!     .record_compute(|compute, _| {
+//!         compute.dispatch(8, 8, 1);
+//!     });
+//!
+//! // A barrier from the decode shader write to this shader read is added automatically
+//! render_graph
+//!     .begin_pass("blur")
+//!     .bind_pipeline(&blur)
+//!     .read_descriptor(0, image)
+//!     .write_descriptor(1, scratch)
+//!     .record_compute(|compute, _| {
+//!         compute.dispatch(8, 8, 1);
+//!     });
+//!
+//! render_graph
+//!     .begin_pass("tonemap")
+//!     .bind_pipeline(&tonemap)
+//!     .read_descriptor(0, scratch)
+//!     .write_descriptor(1, image)
+//!     .record_compute(|compute, _| {
+//!         compute.dispatch(8, 8, 1);
+//!     });
+//! # Ok(()) }
+//! ```
 
 pub mod node;
 pub mod pass_ref;
@@ -13,7 +13,7 @@ use {
             AccelerationStructure, AccelerationStructureGeometry,
             AccelerationStructureGeometryInfo, DeviceOrHostAddress,
         },
-        buffer::{Buffer, BufferSubresourceRange},
+        buffer::{Buffer, BufferSubresourceRange, BufferViewInfo},
         compute::ComputePipeline,
         device::Device,
         graphic::{DepthStencilMode, GraphicPipeline},
@@ -21,18 +21,19 @@ use {
             Image, ImageViewInfo, image_subresource_range_contains,
             image_subresource_range_intersects,
         },
+        is_write_access,
         ray_trace::RayTracePipeline,
         render_pass::ResolveMode,
     },
     ash::vk,
-    log::trace,
+    log::{trace, warn},
     std::{
         cell::RefCell,
         marker::PhantomData,
         ops::{Index, Range},
-        sync::Arc,
+        sync::{Arc, Once, atomic::Ordering},
     },
-    vk_sync::AccessType,
+    vk_sync::{AccessType, BufferBarrier, ImageBarrier, ImageLayout, cmd::pipeline_barrier},
 };
 
 /// Alias for the index of a framebuffer attachment.
@@ -1146,6 +1147,25 @@ impl Index<AnyImageNode> for Bindings<'_> {
     }
 }
 
+/// A resource written by a dispatch which is synchronized by [`Compute::dispatch_then_barrier`].
+#[derive(Clone, Copy, Debug)]
+pub enum BarrierResource {
+    /// A range of a storage buffer.
+    Buffer(AnyBufferNode, BufferSubresourceRange),
+
+    /// A subresource range of a storage image.
+    Image(AnyImageNode, vk::ImageSubresourceRange),
+}
+
+impl BarrierResource {
+    fn node_index(self) -> NodeIndex {
+        match self {
+            Self::Buffer(buffer, _) => buffer.index(),
+            Self::Image(image, _) => image.index(),
+        }
+    }
+}
+
 /// Recording interface for computing commands.
 ///
 /// This structure provides a strongly-typed set of methods which allow compute shader code to be
@@ -1186,6 +1206,84 @@ pub struct Compute<'a> {
 }
 
 impl Compute<'_> {
+    /// Records a barrier which makes shader writes to `buffer` by previous dispatches of this pass
+    /// visible to shader reads by following dispatches.
+    ///
+    /// The render graph synchronizes access to resources between passes, but not between the
+    /// commands recorded within one pass. Multi-pass kernels, such as a scan or reduction which
+    /// dispatches more than once on the same buffer, must record a barrier between dispatches.
+    /// Only `range` of the buffer is synchronized.
+    ///
+    /// See [`Compute::buffer_barrier_read_write`] for kernels which also write the buffer after
+    /// the barrier.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::buffer::{Buffer, BufferInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # use screen_13::graph::RenderGraph;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let buf_info = BufferInfo::device_mem(4096, vk::BufferUsageFlags::STORAGE_BUFFER);
+    /// # let my_buf = Buffer::create(&device, buf_info)?;
+    /// # let info = ComputePipelineInfo::default();
+    /// # let shader = Shader::new_compute([0u8; 1].as_slice());
+    /// # let my_scan_pipeline = Arc::new(ComputePipeline::create(&device, info, shader)?);
+    /// # let mut my_graph = RenderGraph::new();
+    /// let my_buf = my_graph.bind_node(my_buf);
+    ///
+    /// my_graph.begin_pass("scan")
+    ///         .bind_pipeline(&my_scan_pipeline)
+    ///         .write_descriptor(0, my_buf)
+    ///         .record_compute(move |compute, _| {
+    ///             compute.push_constants(&0u32.to_ne_bytes())
+    ///                    .dispatch(16, 1, 1)
+    ///                    .buffer_barrier(my_buf, 0..4096)
+    ///                    .push_constants(&1u32.to_ne_bytes())
+    ///                    .dispatch(16, 1, 1);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    pub fn buffer_barrier(
+        &self,
+        buffer: impl Into<AnyBufferNode>,
+        range: impl Into<BufferSubresourceRange>,
+    ) -> &Self {
+        self.record_buffer_barrier(
+            buffer.into(),
+            range.into(),
+            AccessType::ComputeShaderWrite,
+            AccessType::ComputeShaderReadOther,
+        )
+    }
+
+    /// Records a barrier which makes shader writes to `buffer` by previous dispatches of this pass
+    /// visible to shader reads and writes by following dispatches.
+    ///
+    /// Behaves similarly to [`Compute::buffer_barrier`] except that previous reads are also
+    /// completed before following dispatches write the buffer, which is required when each
+    /// dispatch reads and writes the same elements.
+    pub fn buffer_barrier_read_write(
+        &self,
+        buffer: impl Into<AnyBufferNode>,
+        range: impl Into<BufferSubresourceRange>,
+    ) -> &Self {
+        self.record_buffer_barrier(
+            buffer.into(),
+            range.into(),
+            AccessType::ComputeShaderReadWrite,
+            AccessType::ComputeShaderReadWrite,
+        )
+    }
+
     /// [Dispatch] compute work items.
     ///
     /// When the command is executed, a global workgroup consisting of
@@ -1248,6 +1346,153 @@ impl Compute<'_> {
         self
     }
 
+    /// [Dispatch] enough workgroups to cover `width` work items, rounding up.
+    ///
+    /// The group count is found by dividing `width` by the `local_size_x` of the currently bound
+    /// pipeline, so that no work items at the end are skipped. Shaders should check
+    /// `gl_GlobalInvocationID` against the work size because the last workgroup may be partially
+    /// outside of it.
+    ///
+    /// In debug builds a warning is logged if the pipeline has a multi-dimensional workgroup
+    /// size, because each work item would be processed by more than one invocation. Use
+    /// [`Compute::dispatch_2d`] or [`Compute::dispatch_3d`] for such pipelines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the workgroup size of the pipeline was declared using specialization constants.
+    /// See [`ComputePipeline::local_size`].
+    ///
+    /// [Dispatch]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/vkCmdDispatch.html
+    pub fn dispatch_1d(&self, width: u32) -> &Self {
+        let [local_size_x, ..] = self.local_size(1);
+
+        self.dispatch(width.div_ceil(local_size_x), 1, 1)
+    }
+
+    /// [Dispatch] enough workgroups to cover `width` by `height` work items, rounding up.
+    ///
+    /// Behaves similarly to [`Compute::dispatch_1d`] using both the `local_size_x` and
+    /// `local_size_y` of the currently bound pipeline.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::image::{Image, ImageInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # use screen_13::graph::RenderGraph;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let info = ImageInfo::image_2d(1920, 1080, vk::Format::R8G8B8A8_UNORM, vk::ImageUsageFlags::STORAGE);
+    /// # let my_image = Image::create(&device, info)?;
+    /// # let info = ComputePipelineInfo::default();
+    /// # let shader = Shader::new_compute([0u8; 1].as_slice());
+    /// # let my_compute_pipeline = Arc::new(ComputePipeline::create(&device, info, shader)?);
+    /// # let mut my_graph = RenderGraph::new();
+    /// let my_image = my_graph.bind_node(my_image);
+    /// let my_image_info = my_graph.node_info(my_image);
+    ///
+    /// my_graph.begin_pass("fill my_image")
+    ///         .bind_pipeline(&my_compute_pipeline)
+    ///         .write_descriptor(0, my_image)
+    ///         .record_compute(move |compute, _| {
+    ///             // With an 8x8 workgroup this dispatches 240x135 groups
+    ///             compute.dispatch_2d(my_image_info.width, my_image_info.height);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the workgroup size of the pipeline was declared using specialization constants.
+    /// See [`ComputePipeline::local_size`].
+    ///
+    /// [Dispatch]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/vkCmdDispatch.html
+    pub fn dispatch_2d(&self, width: u32, height: u32) -> &Self {
+        let [local_size_x, local_size_y, _] = self.local_size(2);
+
+        self.dispatch(
+            width.div_ceil(local_size_x),
+            height.div_ceil(local_size_y),
+            1,
+        )
+    }
+
+    /// [Dispatch] enough workgroups to cover `width` by `height` by `depth` work items, rounding
+    /// up.
+    ///
+    /// Behaves similarly to [`Compute::dispatch_1d`] using the full workgroup size of the
+    /// currently bound pipeline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the workgroup size of the pipeline was declared using specialization constants.
+    /// See [`ComputePipeline::local_size`].
+    ///
+    /// [Dispatch]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/vkCmdDispatch.html
+    pub fn dispatch_3d(&self, width: u32, height: u32, depth: u32) -> &Self {
+        let [local_size_x, local_size_y, local_size_z] = self.local_size(3);
+
+        self.dispatch(
+            width.div_ceil(local_size_x),
+            height.div_ceil(local_size_y),
+            depth.div_ceil(local_size_z),
+        )
+    }
+
+    /// [Dispatch] compute work items within a named debug label region.
+    ///
+    /// Behaves similarly to [`Compute::dispatch`] except that the dispatch is grouped under
+    /// `label` in GPU captures taken with tools such as RenderDoc or Nsight. The label is only
+    /// recorded when the device was created with debugging enabled.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # use screen_13::graph::RenderGraph;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let info = ComputePipelineInfo::default();
+    /// # let shader = Shader::new_compute([0u8; 1].as_slice());
+    /// # let my_compute_pipeline = Arc::new(ComputePipeline::create(&device, info, shader)?);
+    /// # let mut my_graph = RenderGraph::new();
+    /// my_graph.begin_pass("simulate")
+    ///         .bind_pipeline(&my_compute_pipeline)
+    ///         .record_compute(move |compute, _| {
+    ///             compute.dispatch_labeled("integrate particles", 64, 1, 1);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [Dispatch]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/vkCmdDispatch.html
+    pub fn dispatch_labeled(
+        &self,
+        label: &str,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) -> &Self {
+        Device::with_debug_label(self.device, self.cmd_buf, label, || {
+            self.dispatch(group_count_x, group_count_y, group_count_z);
+        });
+
+        self
+    }
+
     /// [Dispatch] compute work items with non-zero base values for the workgroup IDs.
     ///
     /// When the command is executed, a global workgroup consisting of
@@ -1284,6 +1529,97 @@ impl Compute<'_> {
         self
     }
 
+    /// [Dispatch] compute work items only if a value computed by the device is non-zero.
+    ///
+    /// Behaves similarly to [`Compute::dispatch`] except that the dispatch is discarded during
+    /// execution when the 32-bit value read from `condition_buf` at `condition_offset` is zero.
+    /// This allows expensive work to be skipped based on the results of earlier passes, such as
+    /// occlusion tests, without reading the results back to the host.
+    ///
+    /// `condition_buf` must have been created with
+    /// [`vk::BufferUsageFlags::CONDITIONAL_RENDERING_EXT`] usage and `condition_offset` must be a
+    /// multiple of four. The pass should access `condition_buf` using [`AccessType::General`] so
+    /// that writes by earlier passes are visible to the conditional rendering stage.
+    ///
+    /// If the device does not support `VK_EXT_conditional_rendering` (see
+    /// [`PhysicalDevice::conditional_rendering_features`]) the dispatch is always executed and a
+    /// warning is logged the first time this happens. Work which must not run when the condition
+    /// is zero should check for support before recording.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::AccessType;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::buffer::Buffer;
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # use screen_13::graph::RenderGraph;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let info = ComputePipelineInfo::default();
+    /// # let shader = Shader::new_compute([0u8; 1].as_slice());
+    /// # let my_compute_pipeline = Arc::new(ComputePipeline::create(&device, info, shader)?);
+    /// # let mut my_graph = RenderGraph::new();
+    /// let condition_buf_flags = vk::BufferUsageFlags::STORAGE_BUFFER
+    ///     | vk::BufferUsageFlags::CONDITIONAL_RENDERING_EXT;
+    /// let condition_buf =
+    ///     Buffer::create_from_slice(&device, condition_buf_flags, &1u32.to_ne_bytes())?;
+    /// let condition_buf_node = my_graph.bind_node(condition_buf);
+    ///
+    /// my_graph.begin_pass("expensive work")
+    ///         .bind_pipeline(&my_compute_pipeline)
+    ///         .access_node(condition_buf_node, AccessType::General)
+    ///         .record_compute(move |compute, _| {
+    ///             compute.dispatch_conditional(condition_buf_node, 0, 64, 64, 1);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [Dispatch]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/vkCmdDispatch.html
+    /// [`PhysicalDevice::conditional_rendering_features`]: crate::driver::physical_device::PhysicalDevice::conditional_rendering_features
+    #[profiling::function]
+    pub fn dispatch_conditional(
+        &self,
+        condition_buf: impl Into<AnyBufferNode>,
+        condition_offset: vk::DeviceSize,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) -> &Self {
+        let condition_buf = condition_buf.into();
+
+        if !self
+            .device
+            .physical_device
+            .conditional_rendering_features
+            .conditional_rendering
+        {
+            static WARN_UNSUPPORTED: Once = Once::new();
+
+            WARN_UNSUPPORTED.call_once(|| {
+                warn!("VK_EXT_conditional_rendering is unsupported: conditional dispatches always execute");
+            });
+        }
+
+        Device::with_conditional_rendering(
+            self.device,
+            self.cmd_buf,
+            *self.bindings[condition_buf],
+            condition_offset,
+            || {
+                self.dispatch(group_count_x, group_count_y, group_count_z);
+            },
+        );
+
+        self
+    }
+
     /// Dispatch compute work items with indirect parameters.
     ///
     /// `dispatch_indirect` behaves similarly to [`Compute::dispatch`] except that the parameters
@@ -1357,6 +1693,245 @@ impl Compute<'_> {
         self
     }
 
+    /// [Dispatch] compute work items and then record a barrier which makes the shader writes to
+    /// each of `resources` visible to shader reads by following dispatches.
+    ///
+    /// This is equivalent to [`Compute::dispatch`] followed by [`Compute::buffer_barrier`] or
+    /// [`Compute::image_barrier`] for each resource, except that all barriers are recorded as a
+    /// single pipeline barrier.
+    ///
+    /// In debug builds a warning is logged for each resource which this pass does not write,
+    /// because the barrier would not be required.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::buffer::{Buffer, BufferInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # use screen_13::graph::RenderGraph;
+    /// # use screen_13::graph::pass_ref::BarrierResource;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let buf_info = BufferInfo::device_mem(4096, vk::BufferUsageFlags::STORAGE_BUFFER);
+    /// # let my_buf = Buffer::create(&device, buf_info)?;
+    /// # let info = ComputePipelineInfo::default();
+    /// # let shader = Shader::new_compute([0u8; 1].as_slice());
+    /// # let my_scan_pipeline = Arc::new(ComputePipeline::create(&device, info, shader)?);
+    /// # let mut my_graph = RenderGraph::new();
+    /// let my_buf = my_graph.bind_node(my_buf);
+    ///
+    /// my_graph.begin_pass("scan")
+    ///         .bind_pipeline(&my_scan_pipeline)
+    ///         .write_descriptor(0, my_buf)
+    ///         .record_compute(move |compute, _| {
+    ///             let resources = [BarrierResource::Buffer(my_buf.into(), (0..4096).into())];
+    ///
+    ///             compute.push_constants(&0u32.to_ne_bytes())
+    ///                    .dispatch_then_barrier(16, 1, 1, &resources)
+    ///                    .push_constants(&1u32.to_ne_bytes())
+    ///                    .dispatch(16, 1, 1);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [Dispatch]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/vkCmdDispatch.html
+    #[profiling::function]
+    pub fn dispatch_then_barrier(
+        &self,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+        resources: &[BarrierResource],
+    ) -> &Self {
+        use std::slice::from_ref;
+
+        self.dispatch(group_count_x, group_count_y, group_count_z);
+
+        if cfg!(debug_assertions) {
+            for resource in resources {
+                let is_written = self
+                    .bindings
+                    .exec
+                    .accesses
+                    .get(&resource.node_index())
+                    .is_some_and(|accesses| {
+                        accesses.iter().any(|access| is_write_access(access.access))
+                    });
+
+                if !is_written {
+                    warn!("barrier recorded for {resource:?} which is not written by this pass");
+                }
+            }
+        }
+
+        let previous_access = AccessType::ComputeShaderWrite;
+        let next_access = AccessType::ComputeShaderReadOther;
+        let mut buffer_barriers = Vec::with_capacity(resources.len());
+        let mut image_barriers = Vec::with_capacity(resources.len());
+
+        for &resource in resources {
+            match resource {
+                BarrierResource::Buffer(buffer, range) => buffer_barriers.push(BufferBarrier {
+                    next_accesses: from_ref(&next_access),
+                    previous_accesses: from_ref(&previous_access),
+                    src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                    dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                    buffer: *self.bindings[buffer],
+                    offset: range.start as _,
+                    size: (range.end - range.start) as _,
+                }),
+                BarrierResource::Image(image, range) => {
+                    image_barriers.push(self.image_barrier_info(
+                        image,
+                        range,
+                        from_ref(&previous_access),
+                        from_ref(&next_access),
+                    ))
+                }
+            }
+        }
+
+        trace!(
+            "      dispatch barrier {} buffers {} images",
+            buffer_barriers.len(),
+            image_barriers.len()
+        );
+
+        pipeline_barrier(
+            self.device,
+            self.cmd_buf,
+            None,
+            &buffer_barriers,
+            &image_barriers,
+        );
+
+        self
+    }
+
+    /// Records a barrier which makes shader writes to `image` by previous dispatches of this pass
+    /// visible to shader reads by following dispatches.
+    ///
+    /// Behaves similarly to [`Compute::buffer_barrier`] for storage images. The image stays in the
+    /// general layout, so descriptors of this pass remain valid. Only `range` of the image is
+    /// synchronized.
+    pub fn image_barrier(
+        &self,
+        image: impl Into<AnyImageNode>,
+        range: vk::ImageSubresourceRange,
+    ) -> &Self {
+        use std::slice::from_ref;
+
+        let previous_access = AccessType::ComputeShaderWrite;
+        let next_access = AccessType::ComputeShaderReadOther;
+
+        trace!(
+            "      image barrier {:?}->{:?} {:?}",
+            previous_access, next_access, range
+        );
+
+        pipeline_barrier(
+            self.device,
+            self.cmd_buf,
+            None,
+            &[],
+            from_ref(&self.image_barrier_info(
+                image.into(),
+                range,
+                from_ref(&previous_access),
+                from_ref(&next_access),
+            )),
+        );
+
+        self
+    }
+
+    fn image_barrier_info<'b>(
+        &self,
+        image: AnyImageNode,
+        range: vk::ImageSubresourceRange,
+        previous_accesses: &'b [AccessType],
+        next_accesses: &'b [AccessType],
+    ) -> ImageBarrier<'b> {
+        ImageBarrier {
+            next_accesses,
+            next_layout: ImageLayout::General,
+            previous_accesses,
+            previous_layout: ImageLayout::General,
+            discard_contents: false,
+            src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            image: *self.bindings[image],
+            range,
+        }
+    }
+
+    fn record_buffer_barrier(
+        &self,
+        buffer: AnyBufferNode,
+        range: BufferSubresourceRange,
+        previous_access: AccessType,
+        next_access: AccessType,
+    ) -> &Self {
+        use std::slice::from_ref;
+
+        trace!(
+            "      buffer barrier {:?}->{:?} {}..{}",
+            previous_access, next_access, range.start, range.end
+        );
+
+        pipeline_barrier(
+            self.device,
+            self.cmd_buf,
+            None,
+            &[BufferBarrier {
+                next_accesses: from_ref(&next_access),
+                previous_accesses: from_ref(&previous_access),
+                src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+                buffer: *self.bindings[buffer],
+                offset: range.start as _,
+                size: (range.end - range.start) as _,
+            }],
+            &[],
+        );
+
+        self
+    }
+
+    /// Returns the workgroup size of the bound pipeline, which is dispatched using `dims`
+    /// dimensions of work items.
+    fn local_size(&self, dims: usize) -> [u32; 3] {
+        let local_size = ComputePipeline::local_size(&self.pipeline).unwrap_or_else(|| {
+            panic!(
+                "workgroup size of pipeline {:?} was not declared using literal values",
+                self.pipeline.name
+            )
+        });
+
+        // A 2D workgroup dispatched over a 1D work size (or similar) repeats every work item
+        if cfg!(debug_assertions)
+            && local_size[dims..].iter().any(|&size| size > 1)
+            && !self
+                .pipeline
+                .dispatch_dims_warned
+                .swap(true, Ordering::Relaxed)
+        {
+            warn!(
+                "{dims}D dispatch of pipeline {:?} with workgroup size {local_size:?}",
+                self.pipeline.name
+            );
+        }
+
+        local_size
+    }
+
     /// Updates push constants.
     ///
     /// Push constants represent a high speed path to modify constant data in pipelines that is
@@ -1416,6 +1991,47 @@ impl Compute<'_> {
     /// # Ok(()) }
     /// ```
     ///
+    /// Larger push constant blocks, such as a matrix followed by scalar values, may be written
+    /// from a `#[repr(C)]` struct. Push constant blocks use `std430` layout rules, so a `mat4`
+    /// followed by a `vec3` and scalars is tightly packed when the matrix is stored as
+    /// `[f32; 16]`. Avoid SIMD-aligned types such as `glam::Mat4` as they round the size of the
+    /// struct up to the next multiple of 16 bytes.
+    ///
+    /// ```
+    /// # inline_spirv::inline_spirv!(r#"
+    /// #version 450
+    ///
+    /// layout(push_constant) uniform PushConstants {
+    ///     layout(offset = 0) mat4 view_proj;
+    ///     layout(offset = 64) vec3 light_dir;
+    ///     layout(offset = 76) float max_distance;
+    ///     layout(offset = 80) uint step_count;
+    /// } push_constants;
+    ///
+    /// void main()
+    /// {
+    /// }
+    /// # "#, comp);
+    /// ```
+    ///
+    /// ```
+    /// # use bytemuck::{Pod, Zeroable};
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy, Pod, Zeroable)]
+    /// struct PushConstants {
+    ///     view_proj: [f32; 16],
+    ///     light_dir: [f32; 3],
+    ///     max_distance: f32,
+    ///     step_count: u32,
+    /// }
+    ///
+    /// assert_eq!(size_of::<PushConstants>(), 84);
+    /// assert_eq!(std::mem::offset_of!(PushConstants, step_count), 80);
+    /// ```
+    ///
+    /// The push constants are then recorded using
+    /// `compute.push_constants(bytemuck::bytes_of(&push_constants))`.
+    ///
     /// [gpuinfo.org]: https://vulkan.gpuinfo.org/displaydevicelimit.php?name=maxPushConstantsSize&platform=all
     pub fn push_constants(&self, data: &[u8]) -> &Self {
         self.push_constants_offset(0, data)
@@ -1490,6 +2106,14 @@ impl Compute<'_> {
             let end = data_end.min(push_const_end);
             let start = offset.max(push_const.offset);
 
+            #[cfg(debug_assertions)]
+            if start != offset || end != data_end {
+                warn!(
+                    "push constants {offset}..{data_end} extend outside of pipeline range {}..{push_const_end}",
+                    push_const.offset
+                );
+            }
+
             if end > start {
                 trace!(
                     "      push constants {:?} {}..{}",
@@ -1501,7 +2125,7 @@ impl Compute<'_> {
                         self.cmd_buf,
                         self.pipeline.layout,
                         vk::ShaderStageFlags::COMPUTE,
-                        push_const.offset,
+                        start,
                         &data[(start - offset) as usize..(end - offset) as usize],
                     );
                 }
@@ -2764,6 +3388,35 @@ where
             .push_node_access(node, access, subresource.into().into());
     }
 
+    /// Informs the pass that the next recorded command buffer will read or write the given buffer
+    /// `node` as a texel buffer at the specified shader descriptor using `access`. The node will be
+    /// interpreted using `view_info`.
+    ///
+    /// Texel buffer descriptors (`imageBuffer` and `samplerBuffer` in GLSL) require a formatted view
+    /// of the buffer and so cannot be bound using the `descriptor` functions. The buffer must have
+    /// been created with `STORAGE_TEXEL_BUFFER` or `UNIFORM_TEXEL_BUFFER` usage, matching the
+    /// descriptor type.
+    ///
+    /// This function must be called for `node` before it is read or written within a `record`
+    /// function. For general purpose access, see [`PipelinePassRef::read_texel_buffer`] or
+    /// [`PipelinePassRef::write_texel_buffer`].
+    pub fn access_texel_buffer<N>(
+        mut self,
+        descriptor: impl Into<Descriptor>,
+        node: N,
+        access: AccessType,
+        view_info: BufferViewInfo,
+    ) -> Self
+    where
+        N: View<Subresource = BufferSubresourceRange>,
+    {
+        self.pass
+            .push_node_access(node, access, BufferSubresourceRange::from(view_info).into());
+        self.push_node_view_bind(node, view_info, descriptor.into());
+
+        self
+    }
+
     /// Binds a Vulkan acceleration structure, buffer, or image to the graph associated with this
     /// pass.
     ///
@@ -2925,6 +3578,27 @@ where
         self.access_node_subrange_mut(node, access, subresource);
     }
 
+    /// Informs the pass that the next recorded command buffer will read the given buffer `node` as
+    /// a texel buffer at the specified shader descriptor. The node will be interpreted using
+    /// `view_info`.
+    ///
+    /// The [`AccessType`] is inferred by the currently bound pipeline. See [`Access`] for details.
+    ///
+    /// This function must be called for `node` before it is read within a `record` function. For
+    /// more specific access, see [`PipelinePassRef::access_texel_buffer`].
+    pub fn read_texel_buffer<N>(
+        self,
+        descriptor: impl Into<Descriptor>,
+        node: N,
+        view_info: BufferViewInfo,
+    ) -> Self
+    where
+        N: View<Subresource = BufferSubresourceRange>,
+    {
+        let access = <T as Access>::DEFAULT_READ;
+        self.access_texel_buffer(descriptor, node, access, view_info)
+    }
+
     /// Finalizes a pass and returns the render graph so that additional passes may be added.
     pub fn submit_pass(self) -> &'a mut RenderGraph {
         self.pass.submit_pass()
@@ -3048,6 +3722,61 @@ where
         let access = <T as Access>::DEFAULT_WRITE;
         self.access_node_subrange_mut(node, access, subresource);
     }
+
+    /// Informs the pass that the next recorded command buffer will write the given buffer `node`
+    /// as a storage texel buffer at the specified shader descriptor. The node will be interpreted
+    /// using `view_info`.
+    ///
+    /// The [`AccessType`] is inferred by the currently bound pipeline. See [`Access`] for details.
+    ///
+    /// This function must be called for `node` before it is written within a `record` function. For
+    /// more specific access, see [`PipelinePassRef::access_texel_buffer`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::buffer::{Buffer, BufferInfo, BufferViewInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # use screen_13::graph::RenderGraph;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let info = ComputePipelineInfo::default();
+    /// # let shader = Shader::new_compute([0u8; 1].as_slice());
+    /// # let my_compute_pipeline = Arc::new(ComputePipeline::create(&device, info, shader)?);
+    /// # let info = BufferInfo::device_mem(4096, vk::BufferUsageFlags::STORAGE_TEXEL_BUFFER);
+    /// # let buffer = Buffer::create(&device, info)?;
+    /// # let mut my_graph = RenderGraph::new();
+    /// # let my_buf_node = my_graph.bind_node(buffer);
+    /// // The shader declares: layout(set = 0, binding = 0, r32f) uniform imageBuffer my_buf;
+    /// let view_info = BufferViewInfo::new(vk::Format::R32_SFLOAT, 0..4096);
+    ///
+    /// my_graph.begin_pass("fill texels")
+    ///         .bind_pipeline(&my_compute_pipeline)
+    ///         .write_texel_buffer(0, my_buf_node, view_info)
+    ///         .record_compute(move |compute, _| {
+    ///             compute.dispatch(1024, 1, 1);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    pub fn write_texel_buffer<N>(
+        self,
+        descriptor: impl Into<Descriptor>,
+        node: N,
+        view_info: BufferViewInfo,
+    ) -> Self
+    where
+        N: View<Subresource = BufferSubresourceRange>,
+    {
+        let access = <T as Access>::DEFAULT_WRITE;
+        self.access_texel_buffer(descriptor, node, access, view_info)
+    }
 }
 
 impl PipelinePassRef<'_, ComputePipeline> {
@@ -5141,6 +5870,9 @@ pub enum ViewType {
 
     /// Buffers may be interpreted as subregions of the same buffer.
     Buffer(Range<vk::DeviceSize>),
+
+    /// Buffers may be interpreted as formatted texel data.
+    TexelBuffer(BufferViewInfo),
 }
 
 impl ViewType {
@@ -5157,6 +5889,13 @@ impl ViewType {
             _ => None,
         }
     }
+
+    pub(super) fn as_texel_buffer(&self) -> Option<&BufferViewInfo> {
+        match self {
+            Self::TexelBuffer(view_info) => Some(view_info),
+            _ => None,
+        }
+    }
 }
 
 impl From<()> for ViewType {
@@ -5171,6 +5910,12 @@ impl From<BufferSubresourceRange> for ViewType {
     }
 }
 
+impl From<BufferViewInfo> for ViewType {
+    fn from(info: BufferViewInfo) -> Self {
+        Self::TexelBuffer(info)
+    }
+}
+
 impl From<ImageViewInfo> for ViewType {
     fn from(info: ImageViewInfo) -> Self {
         Self::Image(info)
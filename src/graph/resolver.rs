@@ -12,6 +12,7 @@ use {
             SubpassDependency, SubpassInfo,
             accel_struct::AccelerationStructure,
             buffer::Buffer,
+            device::Device,
             format_aspect_mask,
             graphic::{DepthStencilMode, GraphicPipeline},
             image::{Image, ImageAccess},
@@ -35,6 +36,9 @@ use {
     vk_sync::{AccessType, BufferBarrier, GlobalBarrier, ImageBarrier, cmd::pipeline_barrier},
 };
 
+#[cfg(debug_assertions)]
+use log::warn;
+
 #[cfg(not(debug_assertions))]
 use std::hint::unreachable_unchecked;
 
@@ -716,45 +720,7 @@ impl Resolver {
                 for (&descriptor_ty, &descriptor_count) in pool_size {
                     debug_assert_ne!(descriptor_count, 0);
 
-                    match descriptor_ty {
-                        vk::DescriptorType::ACCELERATION_STRUCTURE_KHR => {
-                            info.acceleration_structure_count += descriptor_count;
-                        }
-                        vk::DescriptorType::COMBINED_IMAGE_SAMPLER => {
-                            info.combined_image_sampler_count += descriptor_count;
-                        }
-                        vk::DescriptorType::INPUT_ATTACHMENT => {
-                            info.input_attachment_count += descriptor_count;
-                        }
-                        vk::DescriptorType::SAMPLED_IMAGE => {
-                            info.sampled_image_count += descriptor_count;
-                        }
-                        vk::DescriptorType::SAMPLER => {
-                            info.sampler_count += descriptor_count;
-                        }
-                        vk::DescriptorType::STORAGE_BUFFER => {
-                            info.storage_buffer_count += descriptor_count;
-                        }
-                        vk::DescriptorType::STORAGE_BUFFER_DYNAMIC => {
-                            info.storage_buffer_dynamic_count += descriptor_count;
-                        }
-                        vk::DescriptorType::STORAGE_IMAGE => {
-                            info.storage_image_count += descriptor_count;
-                        }
-                        vk::DescriptorType::STORAGE_TEXEL_BUFFER => {
-                            info.storage_texel_buffer_count += descriptor_count;
-                        }
-                        vk::DescriptorType::UNIFORM_BUFFER => {
-                            info.uniform_buffer_count += descriptor_count;
-                        }
-                        vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC => {
-                            info.uniform_buffer_dynamic_count += descriptor_count;
-                        }
-                        vk::DescriptorType::UNIFORM_TEXEL_BUFFER => {
-                            info.uniform_texel_buffer_count += descriptor_count;
-                        }
-                        _ => unimplemented!("{descriptor_ty:?}"),
-                    };
+                    info.add_descriptors(descriptor_ty, descriptor_count);
                 }
             }
         }
@@ -1689,13 +1655,23 @@ impl Resolver {
                             exec.pipeline.as_ref().map(|pipeline| (exec_idx, pipeline))
                         })
                 {
-                    let layouts = pipeline.descriptor_info().layouts.values();
+                    let layouts = pipeline.descriptor_info().layouts.iter();
                     let mut descriptor_sets = Vec::with_capacity(layouts.len());
-                    for descriptor_set_layout in layouts {
-                        descriptor_sets.push(DescriptorPool::allocate_descriptor_set(
+                    for (descriptor_set_idx, descriptor_set_layout) in layouts {
+                        let descriptor_set = DescriptorPool::allocate_descriptor_set(
                             descriptor_pool,
                             descriptor_set_layout,
-                        )?);
+                        )?;
+
+                        if Device::is_debug(&descriptor_pool.device) {
+                            Device::set_debug_name(
+                                &descriptor_pool.device,
+                                *descriptor_set,
+                                &format!("{} set {descriptor_set_idx}", pass.name),
+                            );
+                        }
+
+                        descriptor_sets.push(descriptor_set);
                     }
                     exec_descriptor_sets.insert(exec_idx, descriptor_sets);
                 }
@@ -2980,6 +2956,8 @@ impl Resolver {
             descriptors: Vec<vk::WriteDescriptorSet<'a>>,
             image_infos: Vec<vk::DescriptorImageInfo>,
             image_writes: Vec<IndexWrite<'a>>,
+            texel_buffer_views: Vec<vk::BufferView>,
+            texel_buffer_writes: Vec<IndexWrite<'a>>,
         }
 
         let mut tls = Tls::default();
@@ -3015,6 +2993,18 @@ impl Resolver {
                         image_view_info.aspect_mask = format_aspect_mask(image.info.fmt);
                     }
 
+                    // 3D, array, and cube images must be bound using a view of the same kind the
+                    // shader declared (image3D, image2DArray, samplerCube, etc.)
+                    debug_assert!(
+                        descriptor_info
+                            .image_view_type()
+                            .is_none_or(|view_ty| view_ty == image_view_info.ty),
+                        "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] of pass \"{}\" is declared as {:?} but was bound with a {:?} view",
+                        &pass.name,
+                        descriptor_info.image_view_type().unwrap(),
+                        image_view_info.ty,
+                    );
+
                     let image_view = Image::view(image, image_view_info)?;
                     let image_layout = match descriptor_type {
                         vk::DescriptorType::COMBINED_IMAGE_SAMPLER
@@ -3061,10 +3051,123 @@ impl Resolver {
                             .image_layout(image_layout)
                             .image_view(image_view),
                     );
+                } else if let (Some(buffer), Some(&buffer_view_info)) = (
+                    bound_node.as_driver_buffer(),
+                    view_info
+                        .as_ref()
+                        .and_then(|view_info| view_info.as_texel_buffer()),
+                ) {
+                    #[cfg(debug_assertions)]
+                    {
+                        let usage = match descriptor_type {
+                            vk::DescriptorType::STORAGE_TEXEL_BUFFER => {
+                                vk::BufferUsageFlags::STORAGE_TEXEL_BUFFER
+                            }
+                            vk::DescriptorType::UNIFORM_TEXEL_BUFFER => {
+                                vk::BufferUsageFlags::UNIFORM_TEXEL_BUFFER
+                            }
+                            _ => panic!(
+                                "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] of pass \"{}\" is bound as a texel buffer but is declared as {descriptor_type:?}",
+                                &pass.name,
+                            ),
+                        };
+
+                        debug_assert!(
+                            buffer.info.usage.contains(usage),
+                            "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] of pass \"{}\" is bound to a buffer which was not created with {usage:?} usage",
+                            &pass.name,
+                        );
+                    }
+
+                    let buffer_view = Buffer::view(buffer, buffer_view_info)?;
+
+                    if binding_offset == 0 {
+                        tls.texel_buffer_writes.push(IndexWrite {
+                            idx: tls.texel_buffer_views.len(),
+                            write: vk::WriteDescriptorSet {
+                                dst_set: *descriptor_sets[descriptor_set_idx as usize],
+                                dst_binding,
+                                descriptor_type,
+                                descriptor_count: 1,
+                                ..Default::default()
+                            },
+                        });
+                    } else {
+                        tls.texel_buffer_writes
+                            .last_mut()
+                            .unwrap()
+                            .write
+                            .descriptor_count += 1;
+                    }
+
+                    tls.texel_buffer_views.push(buffer_view);
                 } else if let Some(buffer) = bound_node.as_driver_buffer() {
                     let view_info = view_info.as_ref().unwrap();
                     let buffer_view_info = view_info.as_buffer().unwrap();
 
+                    debug_assert!(
+                        !matches!(
+                            descriptor_type,
+                            vk::DescriptorType::STORAGE_TEXEL_BUFFER
+                                | vk::DescriptorType::UNIFORM_TEXEL_BUFFER
+                        ),
+                        "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] of pass \"{}\" is declared as {descriptor_type:?} and must be bound using a texel buffer function such as write_texel_buffer",
+                        &pass.name,
+                    );
+
+                    #[cfg(debug_assertions)]
+                    {
+                        let limits = &cmd_buf.device.physical_device.properties_v1_0.limits;
+                        let (min_offset_alignment, max_range) = match descriptor_type {
+                            vk::DescriptorType::UNIFORM_BUFFER => (
+                                limits.min_uniform_buffer_offset_alignment,
+                                limits.max_uniform_buffer_range,
+                            ),
+                            vk::DescriptorType::STORAGE_BUFFER => (
+                                limits.min_storage_buffer_offset_alignment,
+                                limits.max_storage_buffer_range,
+                            ),
+                            _ => (1, u32::MAX),
+                        };
+
+                        debug_assert_eq!(
+                            buffer_view_info.start % min_offset_alignment,
+                            0,
+                            "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] of pass \"{}\" is bound at offset {} which is not a multiple of {min_offset_alignment}",
+                            &pass.name,
+                            buffer_view_info.start,
+                        );
+                        debug_assert!(
+                            buffer_view_info.end - buffer_view_info.start
+                                <= max_range as vk::DeviceSize,
+                            "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] of pass \"{}\" is bound with {} bytes which exceeds the limit of {max_range}",
+                            &pass.name,
+                            buffer_view_info.end - buffer_view_info.start,
+                        );
+
+                        // A bound subrange which does not hold whole elements of the runtime-sized
+                        // array usually means the buffer was filled using a different struct
+                        // layout than the one the shader declared; whole buffers are skipped
+                        // because pools round buffer sizes up
+                        let is_whole_buffer = buffer_view_info.start == 0
+                            && (buffer_view_info.end == vk::WHOLE_SIZE
+                                || buffer_view_info.end >= buffer.info.size);
+
+                        if let Some(layout) = descriptor_info
+                            .storage_buffer_layout()
+                            .filter(|_| !is_whole_buffer)
+                        {
+                            let len = buffer_view_info.end - buffer_view_info.start;
+
+                            if len < layout.offset || (len - layout.offset) % layout.stride != 0 {
+                                warn!(
+                                    "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] of pass \"{}\" is bound with {len} bytes, which is not {} bytes followed by whole elements of the {} byte stride the shader declares",
+                                    &pass.name, layout.offset, layout.stride,
+                                );
+                            }
+                        }
+                    }
+
                     if binding_offset == 0 {
                         tls.buffer_writes.push(IndexWrite {
                             idx: tls.buffer_infos.len(),
@@ -3206,13 +3309,21 @@ impl Resolver {
                 write
             },
         ));
+        tls.descriptors
+            .extend(tls.texel_buffer_writes.drain(..).map(
+                |IndexWrite { idx, mut write }| unsafe {
+                    write.p_texel_buffer_view = tls.texel_buffer_views.as_ptr().add(idx);
+                    write
+                },
+            ));
 
         if !tls.descriptors.is_empty() {
             trace!(
-                "  writing {} descriptors ({} buffers, {} images)",
+                "  writing {} descriptors ({} buffers, {} images, {} texel buffers)",
                 tls.descriptors.len(),
                 tls.buffer_infos.len(),
-                tls.image_infos.len()
+                tls.image_infos.len(),
+                tls.texel_buffer_views.len()
             );
 
             unsafe {
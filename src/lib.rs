@@ -339,9 +339,11 @@ pub mod prelude {
                 AccelerationStructureSize, DeviceOrHostAddress,
             },
             ash::vk,
-            buffer::{Buffer, BufferInfo, BufferInfoBuilder, BufferSubresourceRange},
+            buffer::{
+                Buffer, BufferInfo, BufferInfoBuilder, BufferSubresourceRange, BufferViewInfo,
+            },
             compute::{ComputePipeline, ComputePipelineInfo, ComputePipelineInfoBuilder},
-            device::{Device, DeviceInfo, DeviceInfoBuilder},
+            device::{Device, DeviceInfo, DeviceInfoBuilder, MemoryBudget, MemoryHeapBudget},
             graphic::{
                 BlendMode, BlendModeBuilder, DepthStencilMode, DepthStencilModeBuilder,
                 GraphicPipeline, GraphicPipelineInfo, GraphicPipelineInfoBuilder, StencilMode,
@@ -377,7 +379,7 @@ pub mod prelude {
                 AnyAccelerationStructureNode, AnyBufferNode, AnyImageNode, BufferLeaseNode,
                 BufferNode, ImageLeaseNode, ImageNode, SwapchainImageNode,
             },
-            pass_ref::{PassRef, PipelinePassRef},
+            pass_ref::{BarrierResource, PassRef, PipelinePassRef},
         },
         pool::{
             Lease, Pool, PoolInfo, PoolInfoBuilder,